@@ -0,0 +1,172 @@
+use crate::{shuffle, sub_randomness_with_key};
+
+/// Derives an `N` byte nonce (e.g. an IV for encryption tooling) from a randomness and a context.
+///
+/// The context is mixed into the randomness such that different contexts lead to
+/// different nonces. It is prefixed with a dedicated tag, which separates the nonce from
+/// the output of [`rng_for`](crate::rng_for) and [`sub_randomness`](crate::sub_randomness)
+/// for the same randomness and context. The output is deterministic: the same randomness
+/// and context always lead to the same nonce. Only use this where a deterministic nonce is
+/// acceptable, i.e. never use the same randomness and context to encrypt two different
+/// messages with the same key.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_nonce};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // A 96 bit nonce for AES-GCM
+/// let nonce: [u8; 12] = random_nonce(randomness, b"session 42");
+/// assert_ne!(nonce, [0u8; 12]);
+/// ```
+pub fn random_nonce<const N: usize>(randomness: [u8; 32], context: &[u8]) -> [u8; N] {
+    let mut key = Vec::with_capacity(b"_^nonce^_".len() + context.len());
+    key.extend_from_slice(b"_^nonce^_");
+    key.extend_from_slice(context);
+    let mut provider = sub_randomness_with_key(randomness, key);
+    let mut out = [0u8; N];
+    for chunk in out.chunks_mut(32) {
+        let len = chunk.len();
+        chunk.copy_from_slice(&provider.provide()[..len]);
+    }
+    out
+}
+
+/// Reorders the bytes of `data` according to a random permutation derived from the randomness.
+///
+/// The result is the same as [`shuffle`](crate::shuffle) with the same randomness. It can be
+/// undone with [`unpermute_bytes`] using the same randomness and data length.
+///
+/// This is a deterministic obfuscation, not encryption. It provides no confidentiality:
+/// the byte values are unchanged and anyone who knows the randomness can undo it.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{permute_bytes, randomness_from_str, unpermute_bytes};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let permuted = permute_bytes(randomness, b"hello world");
+/// assert_ne!(permuted, b"hello world");
+/// assert_eq!(unpermute_bytes(randomness, &permuted), b"hello world");
+/// ```
+pub fn permute_bytes(randomness: [u8; 32], data: &[u8]) -> Vec<u8> {
+    permutation(randomness, data.len())
+        .into_iter()
+        .map(|source| data[source])
+        .collect()
+}
+
+/// Reverts [`permute_bytes`] for the same randomness.
+///
+/// ## Example
+///
+/// See [`permute_bytes`].
+pub fn unpermute_bytes(randomness: [u8; 32], data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0u8; data.len()];
+    for (position, source) in permutation(randomness, data.len()).into_iter().enumerate() {
+        out[source] = data[position];
+    }
+    out
+}
+
+/// Returns a random permutation of the indices 0..len. The value at position `i` is the
+/// index of the element that is moved to `i`.
+fn permutation(randomness: [u8; 32], len: usize) -> Vec<usize> {
+    shuffle(randomness, (0..len).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    #[test]
+    fn random_nonce_works() {
+        let nonce12: [u8; 12] = random_nonce(RANDOMNESS1, b"ctx");
+        let nonce16: [u8; 16] = random_nonce(RANDOMNESS1, b"ctx");
+        // Reproducible
+        assert_eq!(nonce12, random_nonce::<12>(RANDOMNESS1, b"ctx"));
+        assert_eq!(nonce16, random_nonce::<16>(RANDOMNESS1, b"ctx"));
+        // Shorter nonces are a prefix of longer ones
+        assert_eq!(nonce12, nonce16[..12]);
+
+        // Different contexts lead to different nonces
+        let other12: [u8; 12] = random_nonce(RANDOMNESS1, b"other ctx");
+        let other16: [u8; 16] = random_nonce(RANDOMNESS1, b"other ctx");
+        assert_ne!(nonce12, other12);
+        assert_ne!(nonce16, other16);
+
+        // Different randomness leads to different nonces
+        let other12: [u8; 12] = random_nonce([0xA6; 32], b"ctx");
+        assert_ne!(nonce12, other12);
+
+        // Larger than one sub-randomness
+        let nonce: [u8; 70] = random_nonce(RANDOMNESS1, b"ctx");
+        assert_eq!(nonce[..16], nonce16);
+        assert_ne!(nonce[32..64], nonce[..32]);
+
+        let nonce: [u8; 0] = random_nonce(RANDOMNESS1, b"ctx");
+        assert_eq!(nonce, [0u8; 0]);
+    }
+
+    #[test]
+    fn random_nonce_differs_from_other_derivations() {
+        // The seed of rng_for with the same key
+        let nonce: [u8; 32] = random_nonce(RANDOMNESS1, b"x");
+        assert_ne!(nonce, sub_randomness_with_key(RANDOMNESS1, b"x").provide());
+
+        // The first seed of sub_randomness
+        let nonce: [u8; 32] = random_nonce(RANDOMNESS1, b"_^default^_");
+        assert_ne!(nonce, sub_randomness(RANDOMNESS1).provide());
+
+        // The nonce key is the tag followed by the context
+        let nonce: [u8; 32] = random_nonce(RANDOMNESS1, b"ctx");
+        assert_eq!(
+            nonce,
+            sub_randomness_with_key(RANDOMNESS1, b"_^nonce^_ctx").provide()
+        );
+    }
+
+    #[test]
+    fn permute_bytes_works() {
+        let data: Vec<u8> = (0..=255).collect();
+        let permuted = permute_bytes(RANDOMNESS1, &data);
+
+        // Deterministic
+        assert_eq!(permute_bytes(RANDOMNESS1, &data), permuted);
+        // Same as shuffle
+        assert_eq!(permuted, shuffle(RANDOMNESS1, data.clone()));
+        // Same bytes, different positions
+        assert_ne!(permuted, data);
+        let mut sorted = permuted.clone();
+        sorted.sort();
+        assert_eq!(sorted, data);
+        // Different randomness leads to a different permutation
+        assert_ne!(permute_bytes([0xA6; 32], &data), permuted);
+
+        assert_eq!(permute_bytes(RANDOMNESS1, &[]), Vec::<u8>::new());
+        assert_eq!(permute_bytes(RANDOMNESS1, &[7]), vec![7]);
+    }
+
+    #[test]
+    fn unpermute_bytes_works() {
+        for len in [0, 1, 2, 17, 256, 1000] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7 % 256) as u8).collect();
+            let permuted = permute_bytes(RANDOMNESS1, &data);
+            assert_eq!(unpermute_bytes(RANDOMNESS1, &permuted), data);
+            // Also works in the other direction
+            let unpermuted = unpermute_bytes(RANDOMNESS1, &data);
+            assert_eq!(permute_bytes(RANDOMNESS1, &unpermuted), data);
+        }
+
+        // A different randomness does not undo the permutation
+        let data: Vec<u8> = (0..=255).collect();
+        let permuted = permute_bytes(RANDOMNESS1, &data);
+        assert_ne!(unpermute_bytes([0xA6; 32], &permuted), data);
+    }
+}
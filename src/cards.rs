@@ -0,0 +1,370 @@
+use std::fmt;
+
+use crate::pick;
+
+/// The suit of a playing card
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+}
+
+/// The rank of a playing card. Aces are high.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two = 2,
+    Three = 3,
+    Four = 4,
+    Five = 5,
+    Six = 6,
+    Seven = 7,
+    Eight = 8,
+    Nine = 9,
+    Ten = 10,
+    Jack = 11,
+    Queen = 12,
+    King = 13,
+    Ace = 14,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+}
+
+/// A card of a standard 52 card deck
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+// Displays as e.g. "Q♥" or "10♠"
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rank = match self.rank {
+            Rank::Jack => "J".to_string(),
+            Rank::Queen => "Q".to_string(),
+            Rank::King => "K".to_string(),
+            Rank::Ace => "A".to_string(),
+            other => (other as u8).to_string(),
+        };
+        let suit = match self.suit {
+            Suit::Clubs => '♣',
+            Suit::Diamonds => '♦',
+            Suit::Hearts => '♥',
+            Suit::Spades => '♠',
+        };
+        write!(f, "{rank}{suit}")
+    }
+}
+
+/// The category of a poker hand. This is the result type of [`evaluate_hand`].
+///
+/// Categories are ordered from weakest to strongest. Hands of the same category
+/// are not ranked against each other (no kickers).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HandRank {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+}
+
+/// Returns all 52 cards of a standard deck, ordered by suit and rank.
+fn deck() -> Vec<Card> {
+    Suit::ALL
+        .iter()
+        .flat_map(|suit| {
+            Rank::ALL.iter().map(|rank| Card {
+                rank: *rank,
+                suit: *suit,
+            })
+        })
+        .collect()
+}
+
+/// Deals a poker hand of 5 distinct cards from a standard 52 card deck.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{deal_poker_hand, evaluate_hand, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let hand = deal_poker_hand(randomness);
+/// let rank = evaluate_hand(&hand);
+/// println!("{} {} {} {} {}: {rank:?}", hand[0], hand[1], hand[2], hand[3], hand[4]);
+/// ```
+pub fn deal_poker_hand(randomness: [u8; 32]) -> [Card; 5] {
+    pick(randomness, 5, deck())
+        .try_into()
+        .expect("we picked exactly 5 cards")
+}
+
+/// Evaluates the category of a 5 card poker hand.
+///
+/// An ace counts as high or low in straights, i.e. both 10-J-Q-K-A and A-2-3-4-5
+/// are straights.
+///
+/// Panics if the hand does not consist of exactly 5 cards.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{evaluate_hand, Card, HandRank, Rank, Suit};
+///
+/// let hand = [
+///     Card { rank: Rank::Queen, suit: Suit::Hearts },
+///     Card { rank: Rank::Queen, suit: Suit::Spades },
+///     Card { rank: Rank::Four, suit: Suit::Clubs },
+///     Card { rank: Rank::Four, suit: Suit::Hearts },
+///     Card { rank: Rank::Ace, suit: Suit::Diamonds },
+/// ];
+/// assert_eq!(evaluate_hand(&hand), HandRank::TwoPair);
+/// ```
+pub fn evaluate_hand(hand: &[Card]) -> HandRank {
+    if hand.len() != 5 {
+        panic!("a poker hand must consist of exactly 5 cards");
+    }
+
+    let is_flush = hand.iter().all(|card| card.suit == hand[0].suit);
+
+    let mut ranks: Vec<u8> = hand.iter().map(|card| card.rank as u8).collect();
+    ranks.sort_unstable();
+    let is_wheel = ranks == [2, 3, 4, 5, 14];
+    let is_straight = is_wheel || ranks.windows(2).all(|pair| pair[1] == pair[0] + 1);
+
+    // Sizes of groups of equal rank, largest first
+    let mut groups: Vec<usize> = Vec::with_capacity(5);
+    for (i, rank) in ranks.iter().enumerate() {
+        if i > 0 && ranks[i - 1] == *rank {
+            *groups.last_mut().unwrap() += 1;
+        } else {
+            groups.push(1);
+        }
+    }
+    groups.sort_unstable_by(|a, b| b.cmp(a));
+
+    match (is_straight, is_flush, groups.as_slice()) {
+        (true, true, _) if ranks[0] == Rank::Ten as u8 => HandRank::RoyalFlush,
+        (true, true, _) => HandRank::StraightFlush,
+        (_, _, [4, 1]) => HandRank::FourOfAKind,
+        (_, _, [3, 2]) => HandRank::FullHouse,
+        (_, true, _) => HandRank::Flush,
+        (true, _, _) => HandRank::Straight,
+        (_, _, [3, 1, 1]) => HandRank::ThreeOfAKind,
+        (_, _, [2, 2, 1]) => HandRank::TwoPair,
+        (_, _, [2, 1, 1, 1]) => HandRank::OnePair,
+        _ => HandRank::HighCard,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    fn hand(cards: [(Rank, Suit); 5]) -> [Card; 5] {
+        cards.map(|(rank, suit)| Card { rank, suit })
+    }
+
+    #[test]
+    fn deck_works() {
+        let deck = deck();
+        assert_eq!(deck.len(), 52);
+        for (i, card) in deck.iter().enumerate() {
+            assert!(!deck[..i].contains(card));
+        }
+    }
+
+    #[test]
+    fn deal_poker_hand_works() {
+        for randomness in sub_randomness(RANDOMNESS1).take(100) {
+            let hand = deal_poker_hand(randomness);
+            for (i, card) in hand.iter().enumerate() {
+                assert!(!hand[..i].contains(card));
+            }
+        }
+
+        // Different randomness leads to different hands
+        assert_ne!(deal_poker_hand(RANDOMNESS1), deal_poker_hand([0x77; 32]));
+    }
+
+    #[test]
+    fn evaluate_hand_works() {
+        use Rank::*;
+        use Suit::*;
+
+        let royal_flush = hand([
+            (Ace, Hearts),
+            (King, Hearts),
+            (Queen, Hearts),
+            (Jack, Hearts),
+            (Ten, Hearts),
+        ]);
+        assert_eq!(evaluate_hand(&royal_flush), HandRank::RoyalFlush);
+
+        let straight_flush = hand([
+            (Nine, Clubs),
+            (King, Clubs),
+            (Queen, Clubs),
+            (Jack, Clubs),
+            (Ten, Clubs),
+        ]);
+        assert_eq!(evaluate_hand(&straight_flush), HandRank::StraightFlush);
+
+        let steel_wheel = hand([
+            (Ace, Spades),
+            (Two, Spades),
+            (Three, Spades),
+            (Four, Spades),
+            (Five, Spades),
+        ]);
+        assert_eq!(evaluate_hand(&steel_wheel), HandRank::StraightFlush);
+
+        let four_of_a_kind = hand([
+            (Seven, Clubs),
+            (Seven, Diamonds),
+            (Two, Clubs),
+            (Seven, Hearts),
+            (Seven, Spades),
+        ]);
+        assert_eq!(evaluate_hand(&four_of_a_kind), HandRank::FourOfAKind);
+
+        let full_house = hand([
+            (Three, Clubs),
+            (Jack, Diamonds),
+            (Three, Hearts),
+            (Jack, Hearts),
+            (Three, Spades),
+        ]);
+        assert_eq!(evaluate_hand(&full_house), HandRank::FullHouse);
+
+        let flush = hand([
+            (Two, Diamonds),
+            (Nine, Diamonds),
+            (Queen, Diamonds),
+            (Four, Diamonds),
+            (Ace, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&flush), HandRank::Flush);
+
+        let straight = hand([
+            (Six, Diamonds),
+            (Seven, Clubs),
+            (Eight, Diamonds),
+            (Nine, Spades),
+            (Ten, Hearts),
+        ]);
+        assert_eq!(evaluate_hand(&straight), HandRank::Straight);
+
+        let wheel = hand([
+            (Three, Clubs),
+            (Ace, Diamonds),
+            (Five, Spades),
+            (Two, Hearts),
+            (Four, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&wheel), HandRank::Straight);
+
+        // Aces do not wrap around
+        let no_straight = hand([
+            (Queen, Clubs),
+            (King, Diamonds),
+            (Ace, Spades),
+            (Two, Hearts),
+            (Three, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&no_straight), HandRank::HighCard);
+
+        let three_of_a_kind = hand([
+            (King, Clubs),
+            (King, Diamonds),
+            (King, Spades),
+            (Two, Hearts),
+            (Eight, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&three_of_a_kind), HandRank::ThreeOfAKind);
+
+        let two_pair = hand([
+            (King, Clubs),
+            (King, Diamonds),
+            (Two, Spades),
+            (Two, Hearts),
+            (Eight, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&two_pair), HandRank::TwoPair);
+
+        let one_pair = hand([
+            (King, Clubs),
+            (Nine, Diamonds),
+            (Two, Spades),
+            (Two, Hearts),
+            (Eight, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&one_pair), HandRank::OnePair);
+
+        let high_card = hand([
+            (King, Clubs),
+            (Nine, Diamonds),
+            (Four, Spades),
+            (Two, Hearts),
+            (Eight, Diamonds),
+        ]);
+        assert_eq!(evaluate_hand(&high_card), HandRank::HighCard);
+
+        // Categories are ordered
+        assert!(HandRank::RoyalFlush > HandRank::StraightFlush);
+        assert!(HandRank::FullHouse > HandRank::Flush);
+        assert!(HandRank::OnePair > HandRank::HighCard);
+    }
+
+    #[test]
+    #[should_panic = "a poker hand must consist of exactly 5 cards"]
+    fn evaluate_hand_panics_for_wrong_hand_size() {
+        let hand = deal_poker_hand(RANDOMNESS1);
+        evaluate_hand(&hand[..4]);
+    }
+
+    #[test]
+    fn card_display_works() {
+        let card = Card {
+            rank: Rank::Queen,
+            suit: Suit::Hearts,
+        };
+        assert_eq!(card.to_string(), "Q♥");
+        let card = Card {
+            rank: Rank::Ten,
+            suit: Suit::Spades,
+        };
+        assert_eq!(card.to_string(), "10♠");
+    }
+}
@@ -1,7 +1,17 @@
 use std::fmt;
 
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+use rand::Rng;
+
+use crate::{prng::make_prng, random_decimal};
+
 /// The side of a coin. This is the result type of [`coinflip`]
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///
+/// In JSON a side is serialized as `"heads"` or `"tails"`, such that it can be stored
+/// in contract state or returned in a response.
+#[cw_serde]
+#[derive(Copy, Eq, Hash)]
 pub enum Side {
     Heads = 0,
     Tails = 1,
@@ -28,10 +38,64 @@ impl Side {
     pub fn is_tails(&self) -> bool {
         !self.is_heads()
     }
+
+    /// Creates a side from a bool. `true` is heads and `false` is tails,
+    /// matching [`Side::is_heads`].
+    pub fn from_bool(value: bool) -> Self {
+        if value {
+            Side::Heads
+        } else {
+            Side::Tails
+        }
+    }
+
+    /// Returns the numeric representation of the side, 0 for heads and 1 for tails.
+    /// The inverse is the [`TryFrom<u8>`] implementation.
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns `on_heads` for heads and `on_tails` for tails.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use nois::coinflip;
+    ///
+    /// let randomness: [u8; 32] = [0x77; 32];
+    /// let starting_player = coinflip(randomness).select("Alice", "Bob");
+    /// ```
+    pub fn select<T>(self, on_heads: T, on_tails: T) -> T {
+        match self {
+            Side::Heads => on_heads,
+            Side::Tails => on_tails,
+        }
+    }
+}
+
+impl TryFrom<u8> for Side {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Side::Heads),
+            1 => Ok(Side::Tails),
+            _ => Err(format!(
+                "Invalid side value {value}. Must be 0 (heads) or 1 (tails)"
+            )),
+        }
+    }
 }
 
 /// Takes a randomness and returns the result of a coinflip (heads or tails).
 ///
+/// The side is a single bit drawn from a PRNG seeded with the randomness, just like
+/// [`roll_dice`](crate::roll_dice) draws from [`int_in_range`](crate::int_in_range).
+/// The result is the same as the first element of [`coinflips`].
+///
+/// Note: earlier versions of this library only looked at the parity of the first byte.
+/// The outcome for a given randomness changed with this rework.
+///
 /// ## Example
 ///
 /// ```
@@ -50,13 +114,159 @@ impl Side {
 /// }
 /// ```
 pub fn coinflip(randomness: [u8; 32]) -> Side {
-    if randomness[0] % 2 == 0 {
+    let mut rng = make_prng(randomness);
+    Side::from_bool(rng.gen::<bool>())
+}
+
+/// Takes a randomness and returns the results of `count` independent coinflips.
+///
+/// All sides are drawn from a single PRNG seeded with the randomness. This is cheaper than
+/// calling [`coinflip`] for every element of a [`sub_randomness`](crate::sub_randomness) stream.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{coinflips, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let sides = coinflips(randomness, 100);
+/// let heads = sides.iter().filter(|side| side.is_heads()).count();
+/// println!("{heads} heads out of 100");
+/// ```
+pub fn coinflips(randomness: [u8; 32], count: usize) -> Vec<Side> {
+    let mut rng = make_prng(randomness);
+    (0..count)
+        .map(|_| Side::from_bool(rng.gen::<bool>()))
+        .collect()
+}
+
+/// Takes a randomness and returns the result of a biased coinflip, where heads occurs
+/// with probability `heads_probability`.
+///
+/// In contrast to [`coinflip`] this uses the full randomness: heads is returned if
+/// [`random_decimal`] is less than `heads_probability`. A probability of 1 always leads to
+/// heads and a probability of 0 always leads to tails.
+///
+/// Panics if `heads_probability` is greater than 1.
+///
+/// Passing `Decimal::percent(50)` reproduces the unbiased 50/50 distribution. However, the
+/// result for a given randomness is not necessarily the same as the result of [`coinflip`].
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{coinflip_biased, randomness_from_str, Side};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // 70% chance of heads
+/// let side = coinflip_biased(randomness, Decimal::percent(70));
+/// assert_eq!(side, Side::Heads);
+/// ```
+pub fn coinflip_biased(randomness: [u8; 32], heads_probability: Decimal) -> Side {
+    if heads_probability > Decimal::one() {
+        panic!("heads probability must be in [0, 1]");
+    }
+    if random_decimal(randomness) < heads_probability {
         Side::Heads
     } else {
         Side::Tails
     }
 }
 
+/// Takes a randomness and returns the next state of a boolean that keeps `current` with
+/// probability `stay_probability` and flips otherwise. This adds hysteresis to state
+/// machines that should not flip-flop, e.g. a weather that is sunny for a few rounds.
+///
+/// `current` is kept if [`random_decimal`] is less than `stay_probability`.
+///
+/// Panics if `stay_probability` is greater than 1.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{biased_toggle, randomness_from_str, sub_randomness};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut sunny = true;
+/// for subrand in sub_randomness(randomness).take(10) {
+///     // Stay in the same weather with 80% probability
+///     sunny = biased_toggle(subrand, sunny, Decimal::percent(80));
+/// }
+/// ```
+pub fn biased_toggle(randomness: [u8; 32], current: bool, stay_probability: Decimal) -> bool {
+    if stay_probability > Decimal::one() {
+        panic!("stay probability must be in [0, 1]");
+    }
+    if random_decimal(randomness) < stay_probability {
+        current
+    } else {
+        !current
+    }
+}
+
+/// A coin with a streak-breaking rule: after `max_losses` consecutive losses the next
+/// flip is forced to be a win. Heads is a win and tails is a loss.
+///
+/// This distorts the distribution in favour of heads. Each streak ends with a win after
+/// on average `2 - 2^-max_losses` flips, so the long-run share of heads is
+/// `1 / (2 - 2^-max_losses)`, e.g. about 53.3% for `max_losses = 3`. With `max_losses = 0`
+/// every flip is a win.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, sub_randomness, FairCoin};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Can't lose more than 3 times in a row
+/// let mut coin = FairCoin::new(3);
+/// for subrand in sub_randomness(randomness).take(20) {
+///     let side = coin.flip(subrand);
+///     assert!(coin.losses() <= 3);
+/// }
+/// ```
+#[cw_serde]
+pub struct FairCoin {
+    max_losses: u32,
+    losses: u32,
+}
+
+impl FairCoin {
+    /// Creates a coin that forces a win after `max_losses` consecutive losses.
+    pub fn new(max_losses: u32) -> Self {
+        Self {
+            max_losses,
+            losses: 0,
+        }
+    }
+
+    /// The number of consecutive losses so far.
+    pub fn losses(&self) -> u32 {
+        self.losses
+    }
+
+    /// Flips the coin. Returns heads if the loss streak reached `max_losses` and the
+    /// result of [`coinflip`] otherwise.
+    pub fn flip(&mut self, randomness: [u8; 32]) -> Side {
+        let side = if self.losses >= self.max_losses {
+            Side::Heads
+        } else {
+            coinflip(randomness)
+        };
+        match side {
+            Side::Heads => self.losses = 0,
+            Side::Tails => self.losses += 1,
+        }
+        side
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,6 +297,23 @@ mod tests {
         assert!(!Side::Tails.is_heads());
     }
 
+    #[test]
+    fn side_select_works() {
+        assert_eq!(Side::Heads.select("A", "B"), "A");
+        assert_eq!(Side::Tails.select("A", "B"), "B");
+
+        // Works with non-Copy types
+        let heads = String::from("heads value");
+        let tails = String::from("tails value");
+        assert_eq!(Side::Tails.select(heads, tails), "tails value");
+        let heads = vec![1, 2];
+        let tails = vec![3];
+        assert_eq!(Side::Heads.select(heads, tails), vec![1, 2]);
+
+        assert_eq!(coinflip(RANDOMNESS1).select(1, 2), 2);
+        assert_eq!(coinflip(RANDOMNESS2).select(1, 2), 1);
+    }
+
     #[test]
     fn side_implements_display() {
         let heads = Side::Heads;
@@ -100,13 +327,49 @@ mod tests {
         assert_eq!(tails.to_string(), "tails");
     }
 
+    #[test]
+    fn side_conversions_work() {
+        assert_eq!(Side::from_bool(true), Side::Heads);
+        assert_eq!(Side::from_bool(false), Side::Tails);
+        assert!(Side::from_bool(true).is_heads());
+
+        assert_eq!(Side::Heads.as_u8(), 0);
+        assert_eq!(Side::Tails.as_u8(), 1);
+
+        assert_eq!(Side::try_from(0), Ok(Side::Heads));
+        assert_eq!(Side::try_from(1), Ok(Side::Tails));
+        for side in [Side::Heads, Side::Tails] {
+            assert_eq!(Side::try_from(side.as_u8()), Ok(side));
+        }
+        assert_eq!(
+            Side::try_from(2).unwrap_err(),
+            "Invalid side value 2. Must be 0 (heads) or 1 (tails)"
+        );
+        assert_eq!(
+            Side::try_from(255).unwrap_err(),
+            "Invalid side value 255. Must be 0 (heads) or 1 (tails)"
+        );
+    }
+
+    #[test]
+    fn side_serializes_to_json() {
+        use cosmwasm_std::{from_json, to_json_vec};
+
+        assert_eq!(to_json_vec(&Side::Heads).unwrap(), br#""heads""#);
+        assert_eq!(to_json_vec(&Side::Tails).unwrap(), br#""tails""#);
+        assert_eq!(from_json::<Side>(br#""heads""#).unwrap(), Side::Heads);
+        assert_eq!(from_json::<Side>(br#""tails""#).unwrap(), Side::Tails);
+        from_json::<Side>(br#""Heads""#).unwrap_err();
+        from_json::<Side>(br#""edge""#).unwrap_err();
+    }
+
     #[test]
     fn coinflip_works() {
         let result = coinflip(RANDOMNESS1);
-        assert_eq!(result, Side::Heads);
+        assert_eq!(result, Side::Tails);
 
         let result = coinflip(RANDOMNESS2);
-        assert_eq!(result, Side::Tails);
+        assert_eq!(result, Side::Heads);
 
         let result = coinflip(RANDOMNESS3);
         assert_eq!(result, Side::Tails);
@@ -153,4 +416,197 @@ mod tests {
             assert!(count >= estimation_min && count <= estimation_max);
         }
     }
+
+    #[test]
+    fn coinflips_works() {
+        assert_eq!(coinflips(RANDOMNESS1, 0), Vec::<Side>::new());
+
+        let sides = coinflips(RANDOMNESS1, 50);
+        assert_eq!(sides.len(), 50);
+        assert!(sides.contains(&Side::Heads));
+        assert!(sides.contains(&Side::Tails));
+
+        // Deterministic and shorter outputs are a prefix of longer ones
+        assert_eq!(coinflips(RANDOMNESS1, 50), sides);
+        assert_eq!(coinflips(RANDOMNESS1, 10), sides[..10]);
+
+        // Different randomness leads to different sides
+        assert_ne!(coinflips(RANDOMNESS2, 50), sides);
+    }
+
+    #[test]
+    fn coinflips_distribution_is_uniform() {
+        const TEST_SAMPLE_SIZE: usize = 300_000;
+        const ACCURACY: f32 = 0.01;
+
+        let heads = coinflips(RANDOMNESS1, TEST_SAMPLE_SIZE)
+            .into_iter()
+            .filter(|side| side.is_heads())
+            .count() as i32;
+        let tails = TEST_SAMPLE_SIZE as i32 - heads;
+
+        let estimated_count = (TEST_SAMPLE_SIZE / 2) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        println!("heads: {heads}, tails: {tails}");
+        assert!(heads >= estimation_min && heads <= estimation_max);
+        assert!(tails >= estimation_min && tails <= estimation_max);
+    }
+
+    #[test]
+    fn coinflip_biased_works() {
+        for randomness in [RANDOMNESS1, RANDOMNESS2, RANDOMNESS3, RANDOMNESS4] {
+            assert_eq!(coinflip_biased(randomness, Decimal::zero()), Side::Tails);
+            assert_eq!(coinflip_biased(randomness, Decimal::one()), Side::Heads);
+
+            let side = coinflip_biased(randomness, Decimal::percent(30));
+            let threshold = random_decimal(randomness);
+            assert_eq!(side.is_heads(), threshold < Decimal::percent(30));
+        }
+    }
+
+    #[test]
+    #[should_panic = "heads probability must be in [0, 1]"]
+    fn coinflip_biased_panics_for_probability_greater_than_one() {
+        coinflip_biased(RANDOMNESS1, Decimal::percent(150));
+    }
+
+    #[test]
+    fn coinflip_biased_distribution_is_correct() {
+        /// Throws many biased coins and checks that the share of heads is
+        /// the expected one within a range of 1%
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 300_000;
+        const ACCURACY: f32 = 0.01;
+
+        for percent in [50u64, 70, 90, 5] {
+            let mut heads = 0;
+            for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+                if coinflip_biased(subrand, Decimal::percent(percent)).is_heads() {
+                    heads += 1;
+                }
+            }
+
+            let estimated_count = TEST_SAMPLE_SIZE as f32 * percent as f32 / 100.0;
+            let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+            println!(
+                "{percent}%: {heads}, max: {}, min: {}",
+                estimation_max, estimation_min
+            );
+            assert!(heads >= estimation_min && heads <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn biased_toggle_works() {
+        for randomness in [RANDOMNESS1, RANDOMNESS2, RANDOMNESS3, RANDOMNESS4] {
+            for current in [true, false] {
+                assert_eq!(biased_toggle(randomness, current, Decimal::one()), current);
+                assert_eq!(
+                    biased_toggle(randomness, current, Decimal::zero()),
+                    !current
+                );
+
+                let stays = random_decimal(randomness) < Decimal::percent(40);
+                assert_eq!(
+                    biased_toggle(randomness, current, Decimal::percent(40)),
+                    current == stays
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn biased_toggle_distribution_is_correct() {
+        /// Toggles many times and checks that the stay-rate is
+        /// the expected one within a range of 1%
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 300_000;
+        const ACCURACY: f32 = 0.01;
+
+        for percent in [50u64, 80, 95, 10] {
+            let mut current = false;
+            let mut stays = 0;
+            for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+                let next = biased_toggle(subrand, current, Decimal::percent(percent));
+                if next == current {
+                    stays += 1;
+                }
+                current = next;
+            }
+
+            let estimated_count = TEST_SAMPLE_SIZE as f32 * percent as f32 / 100.0;
+            let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+            println!(
+                "{percent}%: {stays}, max: {}, min: {}",
+                estimation_max, estimation_min
+            );
+            assert!(stays >= estimation_min && stays <= estimation_max);
+        }
+    }
+
+    #[test]
+    #[should_panic = "stay probability must be in [0, 1]"]
+    fn biased_toggle_panics_for_probability_greater_than_one() {
+        biased_toggle(RANDOMNESS1, true, Decimal::percent(101));
+    }
+
+    #[test]
+    fn fair_coin_works() {
+        // RANDOMNESS1 and RANDOMNESS3 are tails
+        let mut coin = FairCoin::new(2);
+        assert_eq!(coin.flip(RANDOMNESS1), Side::Tails);
+        assert_eq!(coin.losses(), 1);
+        assert_eq!(coin.flip(RANDOMNESS3), Side::Tails);
+        assert_eq!(coin.losses(), 2);
+        // Forced win
+        assert_eq!(coin.flip(RANDOMNESS1), Side::Heads);
+        assert_eq!(coin.losses(), 0);
+        assert_eq!(coin.flip(RANDOMNESS1), Side::Tails);
+        assert_eq!(coin.losses(), 1);
+        // Regular win resets the streak
+        assert_eq!(coin.flip(RANDOMNESS2), Side::Heads);
+        assert_eq!(coin.losses(), 0);
+
+        // Always wins
+        let mut coin = FairCoin::new(0);
+        assert_eq!(coin.flip(RANDOMNESS1), Side::Heads);
+        assert_eq!(coin.flip(RANDOMNESS3), Side::Heads);
+    }
+
+    #[test]
+    fn fair_coin_never_exceeds_max_losses() {
+        use crate::sub_randomness::sub_randomness;
+
+        let mut coin = FairCoin::new(3);
+        let mut streak = 0;
+        let mut heads = 0;
+        for subrand in sub_randomness(RANDOMNESS1).take(100_000) {
+            match coin.flip(subrand) {
+                Side::Heads => {
+                    streak = 0;
+                    heads += 1;
+                }
+                Side::Tails => streak += 1,
+            }
+            assert!(streak <= 3);
+        }
+        // 1 / (2 - 2^-3) = 53.3%
+        assert!(heads > 52_800 && heads < 53_800, "{heads}");
+    }
+
+    #[test]
+    fn fair_coin_can_be_stored() {
+        use cosmwasm_std::{from_json, to_json_vec};
+
+        let mut coin = FairCoin::new(3);
+        coin.flip(RANDOMNESS2);
+        let stored = to_json_vec(&coin).unwrap();
+        let restored: FairCoin = from_json(stored).unwrap();
+        assert_eq!(restored, coin);
+    }
 }
@@ -1,4 +1,9 @@
-use crate::int_in_range;
+use rand::Rng;
+
+use crate::{int_in_range, prng::make_prng, sub_randomness};
+
+/// The maximum number of dice in a pool rolled by [`roll_mixed_pool`]
+pub const MAX_POOL_DICE: u32 = 1000;
 
 /// Returns a number from 1-6.
 ///
@@ -34,10 +39,177 @@ pub fn roll_dice(randomness: [u8; 32]) -> u8 {
     int_in_range(randomness, 1, 6)
 }
 
+/// Rolls a pool of differently sided dice and returns the sum, e.g. "1d8 + 2d6".
+///
+/// Each element of `dice` is a `(count, sides)` pair. Every die is rolled with its
+/// own sub-randomness.
+///
+/// Panics if a die has 0 sides, the pool contains more than [`MAX_POOL_DICE`] dice
+/// or the sum exceeds the u32 range.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, roll_mixed_pool};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // 1d8 + 2d6
+/// let damage = roll_mixed_pool(randomness, &[(1, 8), (2, 6)]);
+/// assert!(damage >= 3 && damage <= 20);
+/// ```
+pub fn roll_mixed_pool(randomness: [u8; 32], dice: &[(u32, u32)]) -> u32 {
+    let mut total_dice = 0u32;
+    for (count, sides) in dice {
+        if *sides == 0 {
+            panic!("dice must have at least 1 side");
+        }
+        total_dice = total_dice.saturating_add(*count);
+    }
+    if total_dice > MAX_POOL_DICE {
+        panic!("too many dice in pool");
+    }
+
+    let mut provider = sub_randomness(randomness);
+    let mut sum = 0u32;
+    for (count, sides) in dice {
+        for _ in 0..*count {
+            let roll = int_in_range(provider.provide(), 1, *sides);
+            sum = sum
+                .checked_add(roll)
+                .expect("sum of dice exceeds u32 range");
+        }
+    }
+    sum
+}
+
+/// Rolls `count` dice with `sides` sides each and returns the sum, e.g. 3d6 for damage rolls.
+///
+/// All dice are drawn from a single PRNG seeded with the randomness, which is cheaper
+/// than rolling every die with its own sub-randomness. The sum is returned as a u64
+/// such that large counts do not overflow. A count of 0 returns 0.
+///
+/// Panics if `sides` is 0.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, roll_dice_sum};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // 3d6
+/// let damage = roll_dice_sum(randomness, 3, 6);
+/// assert!(damage >= 3 && damage <= 18);
+/// ```
+pub fn roll_dice_sum(randomness: [u8; 32], count: usize, sides: u32) -> u64 {
+    if sides == 0 {
+        panic!("dice must have at least 1 side");
+    }
+    let mut rng = make_prng(randomness);
+    (0..count).map(|_| rng.gen_range(1..=sides) as u64).sum()
+}
+
+/// Rolls initiative for a group of combatants and returns them in turn order.
+///
+/// Each element of `combatants` is a `(combatant, modifier)` pair. Every combatant rolls
+/// a d20 with its own sub-randomness and adds its modifier. The result contains each
+/// combatant together with its initiative total, sorted from highest to lowest total.
+/// Combatants with equal totals are ordered randomly.
+///
+/// Panics if an initiative total exceeds the i32 range.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, initiative_order};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let order = initiative_order(randomness, &[("rogue", 4), ("fighter", 1), ("goblin", 2)]);
+/// assert_eq!(order.len(), 3);
+/// assert!(order[0].1 >= order[1].1 && order[1].1 >= order[2].1);
+/// ```
+pub fn initiative_order<T: Clone>(randomness: [u8; 32], combatants: &[(T, i32)]) -> Vec<(T, i32)> {
+    let mut provider = sub_randomness(randomness);
+    let mut rolled: Vec<(i32, [u8; 32], &T)> = combatants
+        .iter()
+        .map(|(combatant, modifier)| {
+            let roll: i32 = int_in_range(provider.provide(), 1, 20);
+            let total = roll
+                .checked_add(*modifier)
+                .expect("initiative total exceeds the i32 range");
+            let tiebreaker = provider.provide();
+            (total, tiebreaker, combatant)
+        })
+        .collect();
+    rolled.sort_by(|(total_a, tiebreaker_a, _), (total_b, tiebreaker_b, _)| {
+        total_b.cmp(total_a).then(tiebreaker_a.cmp(tiebreaker_b))
+    });
+    rolled
+        .into_iter()
+        .map(|(total, _, combatant)| (combatant.clone(), total))
+        .collect()
+}
+
+/// The result of a [`skill_check`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// The natural roll of the d20 (1-20)
+    pub roll: u8,
+    /// The roll plus the modifier
+    pub total: i32,
+    /// True if the total meets or beats the difficulty class
+    pub success: bool,
+    /// `Some(true)` for a natural 20 (critical success), `Some(false)` for a natural 1
+    /// (critical failure) and `None` otherwise.
+    pub critical: Option<bool>,
+}
+
+/// Rolls a d20, adds the modifier and compares the total against the difficulty class.
+///
+/// The check succeeds if `roll + modifier >= difficulty_class`. Natural 1s and 20s are
+/// reported in [`CheckResult::critical`] but do not change `success`. Games that treat
+/// criticals as automatic failure or success can apply this rule on top.
+///
+/// Panics if `roll + modifier` exceeds the i32 range.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, skill_check};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Stealth +3 against DC 15
+/// let result = skill_check(randomness, 3, 15);
+/// assert_eq!(result.total, result.roll as i32 + 3);
+/// if result.success {
+///     // sneak past the guards
+/// }
+/// ```
+pub fn skill_check(randomness: [u8; 32], modifier: i32, difficulty_class: i32) -> CheckResult {
+    let roll: u8 = int_in_range(randomness, 1, 20);
+    let total = modifier
+        .checked_add(roll as i32)
+        .expect("skill check total exceeds the i32 range");
+    let critical = match roll {
+        20 => Some(true),
+        1 => Some(false),
+        _ => None,
+    };
+    CheckResult {
+        roll,
+        total,
+        success: total >= difficulty_class,
+        critical,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sub_randomness;
+    use crate::{sub_randomness, RANDOMNESS1};
     use hex_literal::hex;
     use std::collections::HashMap;
 
@@ -49,6 +221,95 @@ mod tests {
         assert_eq!(number, 2);
     }
 
+    #[test]
+    fn roll_mixed_pool_works() {
+        // 1d8 + 2d6
+        let pool = [(1, 8), (2, 6)];
+        for subrand in sub_randomness([0x12; 32]).take(1000) {
+            let sum = roll_mixed_pool(subrand, &pool);
+            assert!((3..=20).contains(&sum));
+        }
+        // Reproducible
+        assert_eq!(
+            roll_mixed_pool([0x12; 32], &pool),
+            roll_mixed_pool([0x12; 32], &pool)
+        );
+
+        // Single die matches int_in_range with the first sub-randomness
+        let randomness = [0x34; 32];
+        let expected = int_in_range(sub_randomness(randomness).provide(), 1, 20);
+        assert_eq!(roll_mixed_pool(randomness, &[(1, 20)]), expected);
+
+        // Empty pool and zero count
+        assert_eq!(roll_mixed_pool(randomness, &[]), 0);
+        assert_eq!(roll_mixed_pool(randomness, &[(0, 6)]), 0);
+
+        // One sided dice
+        assert_eq!(roll_mixed_pool(randomness, &[(7, 1)]), 7);
+
+        // Max pool size
+        let sum = roll_mixed_pool(randomness, &[(MAX_POOL_DICE, 2)]);
+        assert!((MAX_POOL_DICE..=2 * MAX_POOL_DICE).contains(&sum));
+    }
+
+    #[test]
+    #[should_panic = "dice must have at least 1 side"]
+    fn roll_mixed_pool_panics_for_zero_sides() {
+        roll_mixed_pool([0x12; 32], &[(1, 6), (1, 0)]);
+    }
+
+    #[test]
+    #[should_panic = "too many dice in pool"]
+    fn roll_mixed_pool_panics_for_too_many_dice() {
+        roll_mixed_pool([0x12; 32], &[(MAX_POOL_DICE, 6), (1, 6)]);
+    }
+
+    #[test]
+    fn skill_check_works() {
+        // Guaranteed success
+        for subrand in sub_randomness([0x12; 32]).take(100) {
+            let result = skill_check(subrand, 19, 20);
+            assert!(result.success);
+            assert_eq!(result.total, result.roll as i32 + 19);
+        }
+
+        // Guaranteed failure
+        for subrand in sub_randomness([0x12; 32]).take(100) {
+            let result = skill_check(subrand, -5, 16);
+            assert!(!result.success);
+        }
+
+        // Natural 20
+        let result = skill_check([0x7a; 32], 2, 15);
+        assert_eq!(
+            result,
+            CheckResult {
+                roll: 20,
+                total: 22,
+                success: true,
+                critical: Some(true),
+            }
+        );
+
+        // Natural 1
+        let result = skill_check([0x01; 32], 2, 15);
+        assert_eq!(
+            result,
+            CheckResult {
+                roll: 1,
+                total: 3,
+                success: false,
+                critical: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic = "skill check total exceeds the i32 range"]
+    fn skill_check_panics_for_overflow() {
+        skill_check(RANDOMNESS1, i32::MAX, 20);
+    }
+
     #[test]
     fn roll_dice_is_uniformly_distributes() {
         let randomness: [u8; 32] =
@@ -74,4 +335,118 @@ mod tests {
         assert!(sixes > 99_000 && sixes < 101_000);
         assert_eq!(results.values().sum::<usize>(), 600_000);
     }
+
+    #[test]
+    fn initiative_order_works() {
+        assert_eq!(initiative_order::<&str>(RANDOMNESS1, &[]), vec![]);
+
+        let combatants = [("rogue", 4), ("fighter", 1), ("goblin", -1)];
+        let order = initiative_order(RANDOMNESS1, &combatants);
+        assert_eq!(order.len(), 3);
+        for pair in order.windows(2) {
+            assert!(pair[0].1 >= pair[1].1);
+        }
+        for (name, modifier) in combatants {
+            let (_, total) = order.iter().find(|(n, _)| *n == name).unwrap();
+            assert!(*total > modifier && *total <= modifier + 20);
+        }
+
+        // Reproducible
+        assert_eq!(initiative_order(RANDOMNESS1, &combatants), order);
+    }
+
+    #[test]
+    fn initiative_order_favors_higher_modifiers() {
+        let combatants = [("slow", 0), ("fast", 10)];
+        let mut fast_first = 0;
+        for subrand in sub_randomness(RANDOMNESS1).take(10_000) {
+            if initiative_order(subrand, &combatants)[0].0 == "fast" {
+                fast_first += 1;
+            }
+        }
+        // Slow wins outright if it rolls 11+ more (45 of 400 roll pairs) and ties if it rolls
+        // exactly 10 more (10 of 400), which is broken randomly. So fast is first in
+        // (345 + 5) / 400 = 87.5% of the cases.
+        assert!(fast_first > 8_500 && fast_first < 9_200, "{fast_first}");
+    }
+
+    #[test]
+    fn initiative_order_breaks_ties_randomly() {
+        let combatants = [("a", 0), ("b", 0)];
+        let mut ties = 0;
+        let mut a_first_in_ties = 0;
+        for subrand in sub_randomness(RANDOMNESS1).take(40_000) {
+            let order = initiative_order(subrand, &combatants);
+            if order[0].1 == order[1].1 {
+                ties += 1;
+                if order[0].0 == "a" {
+                    a_first_in_ties += 1;
+                }
+            }
+        }
+        // Ties happen in 1 of 20 cases and should be split evenly
+        assert!(ties > 1_800 && ties < 2_200, "{ties}");
+        let share = a_first_in_ties as f64 / ties as f64;
+        assert!(share > 0.44 && share < 0.56, "{share}");
+    }
+
+    #[test]
+    #[should_panic = "initiative total exceeds the i32 range"]
+    fn initiative_order_panics_for_overflow() {
+        initiative_order(RANDOMNESS1, &[("a", i32::MAX)]);
+    }
+
+    #[test]
+    fn roll_dice_sum_works() {
+        assert_eq!(roll_dice_sum(RANDOMNESS1, 0, 6), 0);
+        assert_eq!(roll_dice_sum(RANDOMNESS1, 10, 1), 10);
+
+        for subrand in sub_randomness(RANDOMNESS1).take(1000) {
+            let sum = roll_dice_sum(subrand, 3, 6);
+            assert!((3..=18).contains(&sum));
+        }
+
+        // Large dice and counts do not overflow
+        let sum = roll_dice_sum(RANDOMNESS1, 10_000, u32::MAX);
+        assert!(sum >= 10_000 && sum <= 10_000 * u32::MAX as u64);
+
+        // Reproducible
+        assert_eq!(
+            roll_dice_sum(RANDOMNESS1, 5, 20),
+            roll_dice_sum(RANDOMNESS1, 5, 20)
+        );
+    }
+
+    #[test]
+    #[should_panic = "dice must have at least 1 side"]
+    fn roll_dice_sum_panics_for_zero_sides() {
+        roll_dice_sum(RANDOMNESS1, 3, 0);
+    }
+
+    #[test]
+    fn roll_dice_sum_distribution_matches_3d6() {
+        const TEST_SAMPLE_SIZE: usize = 216_000;
+
+        let mut histogram = HashMap::<u64, usize>::new();
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            *histogram.entry(roll_dice_sum(subrand, 3, 6)).or_default() += 1;
+        }
+
+        // Number of ways to roll each sum out of 216 combinations
+        let ways = [1, 3, 6, 10, 15, 21, 25, 27, 27, 25, 21, 15, 10, 6, 3, 1];
+        for (sum, ways) in (3u64..=18).zip(ways) {
+            let expected = (TEST_SAMPLE_SIZE / 216 * ways) as f64;
+            let count = histogram.get(&sum).copied().unwrap_or_default() as f64;
+            // Allow 4 standard deviations
+            let tolerance = 4.0 * expected.sqrt();
+            assert!(
+                (count - expected).abs() < tolerance,
+                "sum {sum}: {count} (expected {expected})"
+            );
+        }
+
+        // The distribution peaks in the middle
+        assert!(histogram[&10] > histogram[&3] * 20);
+        assert!(histogram[&11] > histogram[&18] * 20);
+    }
 }
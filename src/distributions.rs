@@ -0,0 +1,467 @@
+use cosmwasm_std::Decimal;
+use rand::Rng;
+
+use crate::{int_in_range, prng::make_prng, random_decimal, sub_randomness};
+
+/// Returns a random cell `(x, y)` of a grid with x in `0..width` and y in `0..height`.
+///
+/// The coordinates are derived from two different sub-randomness values,
+/// such that x and y are independent.
+///
+/// Panics if width or height is 0.
+///
+/// ## Example
+///
+/// Find a spawn position on a 64x32 map:
+///
+/// ```
+/// use nois::{randomness_from_str, random_cell};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let (x, y) = random_cell(randomness, 64, 32);
+/// assert!(x < 64);
+/// assert!(y < 32);
+/// ```
+pub fn random_cell(randomness: [u8; 32], width: u32, height: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        panic!("width and height must be greater than 0");
+    }
+    let mut provider = sub_randomness(randomness);
+    let x = int_in_range(provider.provide(), 0, width - 1);
+    let y = int_in_range(provider.provide(), 0, height - 1);
+    (x, y)
+}
+
+/// Samples an index from a discrete distribution given by its probability mass function.
+///
+/// The entries of `pmf` are the probabilities of the individual indices. They must sum up
+/// to 1 with a tolerance of 10^-6 in order to allow for rounding in the input.
+/// Entries with probability 0 are never selected.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{randomness_from_str, sample_pmf};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let pmf = [
+///     Decimal::percent(20),
+///     Decimal::percent(50),
+///     Decimal::percent(30),
+/// ];
+/// let index = sample_pmf(randomness, &pmf).unwrap();
+/// assert!(index < 3);
+/// ```
+pub fn sample_pmf(randomness: [u8; 32], pmf: &[Decimal]) -> Result<usize, String> {
+    if pmf.is_empty() {
+        return Err(String::from("PMF must not be empty"));
+    }
+    // Decimal is unsigned, so all entries are non-negative by construction
+    let mut total = Decimal::zero();
+    for probability in pmf {
+        total = total
+            .checked_add(*probability)
+            .map_err(|_| String::from("PMF must sum up to 1"))?;
+    }
+    let tolerance = Decimal::from_atomics(1u128, 6).unwrap();
+    if total.abs_diff(Decimal::one()) > tolerance {
+        return Err(String::from("PMF must sum up to 1"));
+    }
+
+    // Scale to the actual total such that the walk always ends in a non-zero entry
+    let r = random_decimal(randomness) * total;
+    let mut cumulative = Decimal::zero();
+    for (index, probability) in pmf.iter().enumerate() {
+        cumulative += probability;
+        if r < cumulative {
+            return Ok(index);
+        }
+    }
+    // This point should never be reached
+    panic!("No element selected")
+}
+
+/// Returns a grid of random wall flags, e.g. as a starting point for procedural maze generation.
+///
+/// The result contains `height` rows of `width` cells each, i.e. a cell is accessed via `grid[y][x]`.
+/// Every cell is a wall (`true`) with probability `wall_probability`. All cells are
+/// derived from a single PRNG seeded with the randomness.
+///
+/// Panics if width or height is 0 or the probability is greater than 1.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{randomness_from_str, random_wall_grid};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let grid = random_wall_grid(randomness, 16, 9, Decimal::percent(30));
+/// assert_eq!(grid.len(), 9);
+/// assert_eq!(grid[0].len(), 16);
+/// ```
+pub fn random_wall_grid(
+    randomness: [u8; 32],
+    width: usize,
+    height: usize,
+    wall_probability: Decimal,
+) -> Vec<Vec<bool>> {
+    if width == 0 || height == 0 {
+        panic!("width and height must be greater than 0");
+    }
+    if wall_probability > Decimal::one() {
+        panic!("probability must be in [0, 1]");
+    }
+
+    // A cell is a wall if a random value in [0, 10**18) is below the probability's atomics
+    const ONE: u128 = 1_000_000_000_000_000_000;
+    let threshold = wall_probability.atomics().u128();
+    let mut rng = make_prng(randomness);
+    (0..height)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.gen_range(0..ONE) < threshold)
+                .collect()
+        })
+        .collect()
+}
+
+/// Samples a value from a triangular distribution with lower limit `min`,
+/// upper limit `max` and peak at `mode`.
+///
+/// This uses inverse transform sampling of a single uniform draw, so the same
+/// randomness always leads to the same value.
+///
+/// Panics unless `min <= mode <= max` and all values are finite.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_triangular};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // A guess between 10 and 50, most likely around 20
+/// let value = random_triangular(randomness, 10.0, 20.0, 50.0);
+/// assert!(value >= 10.0 && value <= 50.0);
+/// ```
+pub fn random_triangular(randomness: [u8; 32], min: f64, mode: f64, max: f64) -> f64 {
+    if !(min.is_finite() && mode.is_finite() && max.is_finite()) {
+        panic!("min, mode and max must be finite");
+    }
+    if !(min <= mode && mode <= max) {
+        panic!("min <= mode <= max must hold");
+    }
+    if min == max {
+        return min;
+    }
+
+    let mut rng = make_prng(randomness);
+    let u: f64 = rng.gen(); // in [0, 1)
+    let range = max - min;
+    let mode_cdf = (mode - min) / range;
+    let value = if u < mode_cdf {
+        min + (u * range * (mode - min)).sqrt()
+    } else {
+        max - ((1.0 - u) * range * (max - mode)).sqrt()
+    };
+    // Guard against rounding errors
+    value.clamp(min, max)
+}
+
+/// Generates a one-dimensional random walk on the integers, e.g. for procedural terrain
+/// height-maps.
+///
+/// The walk starts at 0 and takes `steps` steps of `+step_size` or `-step_size`, each
+/// chosen by a fair coin from a single PRNG seeded with the randomness. The result
+/// contains all `steps + 1` positions including the start.
+///
+/// Panics if a position exceeds the i64 range.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_walk};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let heights = random_walk(randomness, 100, 3);
+/// assert_eq!(heights.len(), 101);
+/// assert_eq!(heights[0], 0);
+/// ```
+pub fn random_walk(randomness: [u8; 32], steps: usize, step_size: i64) -> Vec<i64> {
+    let mut rng = make_prng(randomness);
+    let mut positions = Vec::with_capacity(steps + 1);
+    let mut position = 0i64;
+    positions.push(position);
+    for _ in 0..steps {
+        let step = if rng.gen::<bool>() {
+            step_size
+        } else {
+            step_size
+                .checked_neg()
+                .expect("random walk exceeds the i64 range")
+        };
+        position = position
+            .checked_add(step)
+            .expect("random walk exceeds the i64 range");
+        positions.push(position);
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn random_cell_works() {
+        assert_eq!(random_cell(RANDOMNESS1, 1, 1), (0, 0));
+
+        let (x, y) = random_cell(RANDOMNESS1, 1, 1000);
+        assert_eq!(x, 0);
+        assert!(y < 1000);
+
+        let (x, y) = random_cell(RANDOMNESS1, u32::MAX, u32::MAX);
+        assert!(x < u32::MAX && y < u32::MAX);
+
+        // Reproducible
+        assert_eq!(
+            random_cell(RANDOMNESS1, 20, 30),
+            random_cell(RANDOMNESS1, 20, 30)
+        );
+    }
+
+    #[test]
+    #[should_panic = "width and height must be greater than 0"]
+    fn random_cell_panics_for_zero_width() {
+        random_cell(RANDOMNESS1, 0, 5);
+    }
+
+    #[test]
+    #[should_panic = "width and height must be greater than 0"]
+    fn random_cell_panics_for_zero_height() {
+        random_cell(RANDOMNESS1, 5, 0);
+    }
+
+    #[test]
+    fn random_cell_distribution_is_uniform() {
+        /// This test places many points on a 4x3 grid
+        /// and checks that every cell is hit with the same frequency within 3%
+        use std::collections::HashMap;
+
+        const TEST_SAMPLE_SIZE: usize = 120_000;
+        const ACCURACY: f32 = 0.03;
+
+        let mut histogram = HashMap::new();
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let cell = random_cell(subrand, 4, 3);
+            let count = histogram.entry(cell).or_insert(0);
+            *count += 1;
+        }
+
+        assert_eq!(histogram.len(), 12);
+        let estimated_count = (TEST_SAMPLE_SIZE / 12) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        for (cell, count) in histogram {
+            println!("{cell:?}: {count}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn sample_pmf_works() {
+        let index = sample_pmf(RANDOMNESS1, &[Decimal::one()]).unwrap();
+        assert_eq!(index, 0);
+
+        // Zero entries are never selected
+        let pmf = [Decimal::zero(), Decimal::one(), Decimal::zero()];
+        for subrand in sub_randomness(RANDOMNESS1).take(100) {
+            assert_eq!(sample_pmf(subrand, &pmf).unwrap(), 1);
+        }
+
+        // Small rounding errors are tolerated
+        let third = Decimal::from_atomics(333_333_333_333_333_333u128, 18).unwrap();
+        let index = sample_pmf(RANDOMNESS1, &[third, third, third]).unwrap();
+        assert!(index < 3);
+    }
+
+    #[test]
+    fn sample_pmf_fails_for_invalid_pmf() {
+        let err = sample_pmf(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "PMF must not be empty");
+
+        let err =
+            sample_pmf(RANDOMNESS1, &[Decimal::percent(20), Decimal::percent(30)]).unwrap_err();
+        assert_eq!(err, "PMF must sum up to 1");
+
+        let err =
+            sample_pmf(RANDOMNESS1, &[Decimal::percent(60), Decimal::percent(60)]).unwrap_err();
+        assert_eq!(err, "PMF must sum up to 1");
+
+        let err = sample_pmf(RANDOMNESS1, &[Decimal::MAX, Decimal::MAX]).unwrap_err();
+        assert_eq!(err, "PMF must sum up to 1");
+    }
+
+    #[test]
+    fn sample_pmf_distribution_matches() {
+        /// Samples many times from a 3 entry PMF and checks
+        /// that the frequencies match the probabilities within 2%
+        const TEST_SAMPLE_SIZE: usize = 200_000;
+        const ACCURACY: f32 = 0.02;
+
+        let pmf = [
+            Decimal::percent(15),
+            Decimal::percent(60),
+            Decimal::percent(25),
+        ];
+        let expected = [0.15f32, 0.6, 0.25];
+
+        let mut histogram = [0i32; 3];
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            histogram[sample_pmf(subrand, &pmf).unwrap()] += 1;
+        }
+
+        for (index, count) in histogram.into_iter().enumerate() {
+            let estimated_count = TEST_SAMPLE_SIZE as f32 * expected[index];
+            let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+            println!("{index}: {count}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn random_wall_grid_works() {
+        let grid = random_wall_grid(RANDOMNESS1, 7, 3, Decimal::percent(50));
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 7));
+
+        // Reproducible
+        assert_eq!(
+            grid,
+            random_wall_grid(RANDOMNESS1, 7, 3, Decimal::percent(50))
+        );
+
+        // Extreme probabilities
+        let grid = random_wall_grid(RANDOMNESS1, 10, 10, Decimal::zero());
+        assert!(grid.iter().flatten().all(|wall| !wall));
+        let grid = random_wall_grid(RANDOMNESS1, 10, 10, Decimal::one());
+        assert!(grid.iter().flatten().all(|wall| *wall));
+    }
+
+    #[test]
+    fn random_wall_grid_density_converges() {
+        const WIDTH: usize = 400;
+        const HEIGHT: usize = 250;
+
+        for percent in [10u64, 35, 80] {
+            let grid = random_wall_grid(RANDOMNESS1, WIDTH, HEIGHT, Decimal::percent(percent));
+            let walls = grid.iter().flatten().filter(|wall| **wall).count();
+            let density = walls as f64 / (WIDTH * HEIGHT) as f64;
+            println!("{percent}%: {density}");
+            assert!((density - percent as f64 / 100.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    #[should_panic = "width and height must be greater than 0"]
+    fn random_wall_grid_panics_for_zero_dimension() {
+        random_wall_grid(RANDOMNESS1, 0, 3, Decimal::percent(50));
+    }
+
+    #[test]
+    #[should_panic = "probability must be in [0, 1]"]
+    fn random_wall_grid_panics_for_probability_greater_than_one() {
+        random_wall_grid(RANDOMNESS1, 3, 3, Decimal::percent(101));
+    }
+
+    #[test]
+    fn random_triangular_works() {
+        // Degenerate distribution
+        assert_eq!(random_triangular(RANDOMNESS1, 3.0, 3.0, 3.0), 3.0);
+
+        // Mode at the bounds
+        for subrand in sub_randomness(RANDOMNESS1).take(1000) {
+            let value = random_triangular(subrand, 0.0, 0.0, 1.0);
+            assert!((0.0..=1.0).contains(&value));
+            let value = random_triangular(subrand, -5.0, 5.0, 5.0);
+            assert!((-5.0..=5.0).contains(&value));
+        }
+
+        // Reproducible
+        assert_eq!(
+            random_triangular(RANDOMNESS1, 1.0, 2.0, 4.0),
+            random_triangular(RANDOMNESS1, 1.0, 2.0, 4.0)
+        );
+    }
+
+    #[test]
+    #[should_panic = "min <= mode <= max must hold"]
+    fn random_triangular_panics_for_mode_out_of_range() {
+        random_triangular(RANDOMNESS1, 0.0, 11.0, 10.0);
+    }
+
+    #[test]
+    #[should_panic = "min, mode and max must be finite"]
+    fn random_triangular_panics_for_nan() {
+        random_triangular(RANDOMNESS1, 0.0, f64::NAN, 10.0);
+    }
+
+    #[test]
+    fn random_triangular_distribution_peaks_at_mode() {
+        /// Samples many values in [0, 10] with mode 7 and checks that the bin
+        /// containing the mode is the most frequent one
+        const TEST_SAMPLE_SIZE: usize = 100_000;
+
+        let mut histogram = [0usize; 10];
+        let mut sum = 0.0;
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let value = random_triangular(subrand, 0.0, 7.0, 10.0);
+            assert!((0.0..=10.0).contains(&value));
+            histogram[(value as usize).min(9)] += 1;
+            sum += value;
+        }
+        println!("{histogram:?}");
+
+        let max_bin = (0..10).max_by_key(|bin| histogram[*bin]).unwrap();
+        assert!(max_bin == 6 || max_bin == 7);
+
+        // The mean of a triangular distribution is (min + mode + max) / 3
+        let mean = sum / TEST_SAMPLE_SIZE as f64;
+        assert!((mean - 17.0 / 3.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn random_walk_works() {
+        let walk = random_walk(RANDOMNESS1, 1000, 7);
+        assert_eq!(walk.len(), 1001);
+        assert_eq!(walk[0], 0);
+        for pair in walk.windows(2) {
+            assert_eq!((pair[1] - pair[0]).abs(), 7);
+        }
+        // Both directions are taken
+        assert!(walk.windows(2).any(|pair| pair[1] > pair[0]));
+        assert!(walk.windows(2).any(|pair| pair[1] < pair[0]));
+
+        // Reproducible
+        assert_eq!(random_walk(RANDOMNESS1, 1000, 7), walk);
+        assert_ne!(random_walk([0x77; 32], 1000, 7), walk);
+
+        assert_eq!(random_walk(RANDOMNESS1, 0, 7), vec![0]);
+        assert_eq!(random_walk(RANDOMNESS1, 5, 0), vec![0; 6]);
+    }
+
+    #[test]
+    #[should_panic = "random walk exceeds the i64 range"]
+    fn random_walk_panics_for_overflow() {
+        random_walk(RANDOMNESS1, 10, i64::MAX);
+    }
+}
@@ -1,11 +1,14 @@
-use std::ops::{Add, AddAssign};
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Bound, RangeBounds};
 
+use cosmwasm_std::Uint128;
 use rand::{
     distributions::{uniform::SampleUniform, Distribution, Uniform},
     Rng,
 };
 
-use crate::prng::make_prng;
+use crate::{choose_branch, prng::make_prng};
 
 /// Derives a random integer in the range \[begin, end], i.e. including both bounds.
 /// Use this method to avoid a modulo bias.
@@ -51,20 +54,123 @@ use crate::prng::make_prng;
 /// assert_eq!(int_in_range(randomness, 1u16, 20), 8);
 /// assert_eq!(int_in_range(randomness, 1u16, 19), 7);
 /// ```
+///
+/// Panics if `end` is less than `begin`. Use [`try_int_in_range`] to handle this case
+/// as an error, e.g. for user-supplied bounds.
 pub fn int_in_range<T>(randomness: [u8; 32], begin: T, end: T) -> T
 where
     T: SampleUniform + Int,
 {
+    if end < begin {
+        panic!("cannot sample empty range: end is less than begin");
+    }
     let mut rng = make_prng(randomness);
     rng.gen_range(begin..=end)
 }
 
+/// The error returned by [`try_int_in_range`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// The end of the range is less than its begin
+    EmptyRange { begin: String, end: String },
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::EmptyRange { begin, end } => write!(
+                f,
+                "cannot sample empty range: end ({end}) is less than begin ({begin})"
+            ),
+        }
+    }
+}
+
+/// Derives a random integer in the range \[begin, end] like [`int_in_range`] but returns
+/// an error instead of panicking if `end` is less than `begin`.
+///
+/// This allows contracts to validate user-supplied bounds without aborting the transaction.
+/// For valid bounds the result is the same as the result of [`int_in_range`].
+///
+/// ## Example
+///
+/// ```
+/// use nois::{int_in_range, try_int_in_range, RangeError};
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let value = try_int_in_range(randomness, 1, 6).unwrap();
+/// assert_eq!(value, int_in_range(randomness, 1, 6));
+///
+/// let err = try_int_in_range(randomness, 6, 1).unwrap_err();
+/// assert_eq!(err, RangeError::EmptyRange { begin: "6".to_string(), end: "1".to_string() });
+/// assert_eq!(err.to_string(), "cannot sample empty range: end (1) is less than begin (6)");
+/// ```
+pub fn try_int_in_range<T>(randomness: [u8; 32], begin: T, end: T) -> Result<T, RangeError>
+where
+    T: SampleUniform + Int + Display,
+{
+    if end < begin {
+        return Err(RangeError::EmptyRange {
+            begin: begin.to_string(),
+            end: end.to_string(),
+        });
+    }
+    Ok(int_in_range(randomness, begin, end))
+}
+
+/// Derives a random integer in the given range. This works like [`int_in_range`] but
+/// accepts Rust's range syntax, i.e. `a..b`, `a..=b`, `a..`, `..b`, `..=b` and `..`.
+///
+/// Exclusive bounds are converted to inclusive ones, such that `int_in_range_bounds(r, a..=b)`
+/// is the same as `int_in_range(r, a, b)` and `int_in_range_bounds(r, a..b)` is the same
+/// as `int_in_range(r, a, b - 1)`. Open bounds are replaced by the type's minimum and maximum.
+///
+/// Panics if the range is empty.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{int_in_range, int_in_range_bounds};
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let dice = int_in_range_bounds(randomness, 1..=6);
+/// assert_eq!(dice, int_in_range(randomness, 1, 6));
+///
+/// // An index into a list
+/// let list = ["a", "b", "c"];
+/// let index = int_in_range_bounds(randomness, 0..list.len());
+/// assert!(index < list.len());
+/// ```
+pub fn int_in_range_bounds<T, R>(randomness: [u8; 32], range: R) -> T
+where
+    T: SampleUniform + BoundedInt,
+    R: RangeBounds<T>,
+{
+    let begin = match range.start_bound() {
+        Bound::Included(begin) => Some(*begin),
+        Bound::Excluded(begin) => begin.checked_increment(),
+        Bound::Unbounded => Some(T::MIN),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => Some(*end),
+        Bound::Excluded(end) => end.checked_decrement(),
+        Bound::Unbounded => Some(T::MAX),
+    };
+    match (begin, end) {
+        (Some(begin), Some(end)) if begin <= end => int_in_range(randomness, begin, end),
+        _ => panic!("range must not be empty"),
+    }
+}
+
 /// Derives random integers in the range [begin, end], i.e. including both bounds.
 /// Use this method to avoid a modulo bias.
 /// The resulting vector will contain exactly `count` elements.
 ///
 /// Using this is potentially more efficient than multiple calls of [`int_in_range`].
 ///
+/// The values are drawn independently and can contain duplicates. Use
+/// [`distinct_ints_in_range`] for draws without replacement, e.g. raffles and lotteries.
+///
 /// ## Example
 ///
 /// A round of [Yahtzee](https://en.wikipedia.org/wiki/Yahtzee) with five dices:
@@ -96,6 +202,315 @@ where
     out
 }
 
+/// Draws an index `i` with probability `weights[i] / sum(weights)`, e.g. a tier of a loot table.
+///
+/// This is an alias of [`choose_branch`] for code that reads better with an integer
+/// oriented name. Both return the same index for the same randomness and weights, which is
+/// the position of the element that [`select_from_weighted`](crate::select_from_weighted)
+/// selects. Returning the index allows looking up parallel arrays without cloning payloads.
+///
+/// Returns an error if the list is empty, a weight is 0 or the total weight exceeds the u32 range.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, weighted_int};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let tier_names = ["common", "rare", "epic"];
+/// let tier_rewards = [10, 50, 200];
+/// let tier = weighted_int(randomness, &[80, 15, 5]).unwrap();
+/// println!("{}: {}", tier_names[tier], tier_rewards[tier]);
+/// ```
+pub fn weighted_int(randomness: [u8; 32], weights: &[u32]) -> Result<usize, String> {
+    choose_branch(randomness, weights)
+}
+
+/// Panics if `value` is not in the range \[begin, end], i.e. including both bounds.
+///
+/// This allows contracts to self-check derived values (e.g. from [`int_in_range`]) before
+/// using them in financial logic.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{assert_in_range, int_in_range};
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let payout_percent = int_in_range(randomness, 10u8, 90);
+/// assert_in_range(payout_percent, 10, 90);
+/// ```
+pub fn assert_in_range<T>(value: T, begin: T, end: T)
+where
+    T: Int + Display,
+{
+    if value < begin || value > end {
+        panic!("value {value} is out of range [{begin}, {end}]");
+    }
+}
+
+/// Derives a random token amount in the range \[min, max], i.e. including both bounds.
+///
+/// This is useful for randomized fees or tips within a band. It is the same as
+/// calling [`int_in_range`] with u128 values.
+///
+/// Panics if max is less than min.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Uint128;
+/// use nois::random_coin_amount;
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let tip = random_coin_amount(randomness, Uint128::new(1_000), Uint128::new(5_000));
+/// assert!(tip >= Uint128::new(1_000) && tip <= Uint128::new(5_000));
+/// ```
+pub fn random_coin_amount(randomness: [u8; 32], min: Uint128, max: Uint128) -> Uint128 {
+    if max < min {
+        panic!("max must be greater than or equal to min");
+    }
+    Uint128::new(int_in_range(randomness, min.u128(), max.u128()))
+}
+
+/// Derives `count` random digits in the given `base`, i.e. each digit is in the range \[0, base).
+///
+/// All digits are derived from a single PRNG seeded with the randomness. This is useful for
+/// generating codes such as PINs (base 10) or hex strings (base 16).
+///
+/// Panics if `base` is less than 2.
+///
+/// ## Example
+///
+/// Generate a 6 digit PIN:
+///
+/// ```
+/// use nois::random_digits;
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let pin: String = random_digits(randomness, 10, 6)
+///     .into_iter()
+///     .map(|digit| char::from_digit(digit, 10).unwrap())
+///     .collect();
+/// assert_eq!(pin.len(), 6);
+/// ```
+pub fn random_digits(randomness: [u8; 32], base: u32, count: usize) -> Vec<u32> {
+    if base < 2 {
+        panic!("base must be at least 2");
+    }
+    ints_in_range(randomness, count, 0, base - 1)
+}
+
+/// Derives `count` distinct random integers in the range [begin, end], i.e. including both bounds.
+///
+/// In contrast to [`ints_in_range`] every value occurs at most once, which is what raffles
+/// and lottery draws need. Every combination of values is equally likely and the order
+/// of the output is random. Signed and mixed-sign ranges such as `-100..=100` are supported
+/// and the range may span the full domain of the integer type.
+///
+/// This does not materialize the range, so it is efficient for huge ranges as long as `count` is small.
+///
+/// Panics if the range is empty or `count` exceeds the number of integers in the range.
+///
+/// ## Example
+///
+/// Draw 6 out of 49:
+///
+/// ```
+/// use nois::distinct_ints_in_range;
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let numbers = distinct_ints_in_range(randomness, 6, 1, 49);
+/// assert_eq!(numbers.len(), 6);
+/// for (i, n) in numbers.iter().enumerate() {
+///     assert!(*n >= 1 && *n <= 49);
+///     assert!(!numbers[..i].contains(n));
+/// }
+/// ```
+pub fn distinct_ints_in_range<T>(randomness: [u8; 32], count: usize, begin: T, end: T) -> Vec<T>
+where
+    T: DistinctInt,
+{
+    if end < begin {
+        panic!("cannot sample empty range");
+    }
+    // The largest offset from `begin`. The range size is `span + 1`, which might not fit in a u128.
+    let span = T::offset_between(begin, end);
+    if count == 0 {
+        return Vec::new();
+    }
+    if (count - 1) as u128 > span {
+        panic!("attempt to draw more distinct integers than the range size");
+    }
+
+    // Robert Floyd's algorithm selects a uniformly distributed subset of offsets
+    // without materializing the range.
+    let mut rng = make_prng(randomness);
+    let mut seen = BTreeSet::<u128>::new();
+    let mut offsets = Vec::with_capacity(count);
+    let lowest = span - (count - 1) as u128;
+    for j in lowest..=span {
+        let t = rng.gen_range(0..=j);
+        let offset = if seen.contains(&t) { j } else { t };
+        seen.insert(offset);
+        offsets.push(offset);
+    }
+
+    // The order of Floyd's output is not uniform, so we shuffle it
+    for i in (1..offsets.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        offsets.swap(i, j);
+    }
+
+    offsets
+        .into_iter()
+        .map(|offset| begin.add_offset(offset))
+        .collect()
+}
+
+/// Returns a random step in the range \[1, n) that is coprime with `n`.
+///
+/// Repeatedly adding the step modulo `n` visits all values in \[0, n) exactly once before
+/// returning to the start. This is useful for space-filling traversals, e.g. walking over
+/// all cells of a procedurally generated dungeon in a random looking order.
+///
+/// The step is selected uniformly from all coprime candidates using rejection sampling.
+///
+/// Panics if `n` is less than 2.
+///
+/// ## Example
+///
+/// ```
+/// use nois::random_coprime_step;
+///
+/// # let randomness: [u8; 32] = [0x77; 32];
+/// let cells = 10;
+/// let step = random_coprime_step(randomness, cells);
+/// let mut position = 0;
+/// let mut visited = vec![false; cells as usize];
+/// for _ in 0..cells {
+///     visited[position as usize] = true;
+///     position = (position + step) % cells;
+/// }
+/// assert!(visited.iter().all(|v| *v));
+/// ```
+pub fn random_coprime_step(randomness: [u8; 32], n: u64) -> u64 {
+    if n < 2 {
+        panic!("n must be at least 2");
+    }
+    let mut rng = make_prng(randomness);
+    loop {
+        let candidate = rng.gen_range(1..n);
+        if gcd(candidate, n) == 1 {
+            return candidate;
+        }
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns true if reducing a uniformly distributed `source_bits` bit value modulo `range_size`
+/// leads to a biased result, i.e. if `2^source_bits % range_size != 0`.
+///
+/// This is the case unless `range_size` is a power of two not exceeding `2^source_bits`.
+/// Use [`int_in_range`] to get unbiased results in all cases.
+///
+/// Panics if `range_size` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::has_modulo_bias;
+///
+/// // `randomness[0] % 6` is biased
+/// assert!(has_modulo_bias(6, 8));
+/// // `randomness[0] % 16` is not
+/// assert!(!has_modulo_bias(16, 8));
+/// ```
+pub fn has_modulo_bias(range_size: u64, source_bits: u32) -> bool {
+    if range_size == 0 {
+        panic!("range size must not be 0");
+    }
+    !(range_size.is_power_of_two() && range_size.trailing_zeros() <= source_bits)
+}
+
+/// Returns the expected number of distinct values when drawing `count` times with replacement
+/// from a range of `range_size` values, e.g. using [`ints_in_range`].
+///
+/// This is `n * (1 - (1 - 1/n)^k)` for range size `n` and count `k`. It helps deciding
+/// whether duplicates are acceptable or [`distinct_ints_in_range`] should be used.
+///
+/// Panics if `range_size` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::expected_distinct;
+///
+/// // Rolling 6 dice leads to 3.99 different numbers on average
+/// let expected = expected_distinct(6, 6);
+/// assert!((expected - 3.99).abs() < 0.01);
+/// ```
+pub fn expected_distinct(count: usize, range_size: u64) -> f64 {
+    if range_size == 0 {
+        panic!("range size must not be 0");
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    let n = range_size as f64;
+    // (1 - 1/n)^k computed as exp(k * ln(1 - 1/n)) in a numerically stable way for large n
+    -n * (count as f64 * (-1.0 / n).ln_1p()).exp_m1()
+}
+
+/// Returns the number of random bits needed to distinguish `num_outcomes` outcomes,
+/// i.e. `ceil(log2(num_outcomes))`.
+///
+/// Note that [`has_modulo_bias`] tells if the outcomes can be derived from this number of
+/// bits without bias.
+///
+/// Panics if `num_outcomes` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::bits_needed;
+///
+/// // A die
+/// assert_eq!(bits_needed(6), 3);
+/// // 6 out of 49
+/// assert_eq!(bits_needed(13_983_816), 24);
+/// ```
+pub fn bits_needed(num_outcomes: u64) -> u32 {
+    if num_outcomes == 0 {
+        panic!("number of outcomes must not be 0");
+    }
+    u64::BITS - (num_outcomes - 1).leading_zeros()
+}
+
+/// Returns the number of outcomes that can be distinguished with `bits` random bits,
+/// i.e. `2^bits`. This is the inverse of [`bits_needed`] for powers of two.
+///
+/// Panics if `bits` is greater than 63.
+///
+/// ## Example
+///
+/// ```
+/// use nois::outcomes_from_bits;
+///
+/// assert_eq!(outcomes_from_bits(8), 256);
+/// ```
+pub fn outcomes_from_bits(bits: u32) -> u64 {
+    1u64.checked_shl(bits)
+        .expect("number of outcomes exceeds the u64 range")
+}
+
 /// A trait to restrict int types for [`int_in_range`]
 pub trait Int: PartialOrd + Default + Copy {}
 
@@ -174,8 +589,87 @@ impl Uint for usize {
     }
 }
 
+/// A trait to map integers to offsets from a lower bound for [`distinct_ints_in_range`]
+pub trait DistinctInt: Int {
+    /// Returns `end - begin` as an unsigned value. This cannot overflow for `begin <= end`.
+    fn offset_between(begin: Self, end: Self) -> u128;
+
+    /// Returns `self + offset`. The caller must ensure the result is in range.
+    fn add_offset(self, offset: u128) -> Self;
+}
+
+macro_rules! impl_distinct_int {
+    ($($t:ty => $unsigned:ty),* $(,)?) => {
+        $(
+            impl DistinctInt for $t {
+                #[inline]
+                fn offset_between(begin: Self, end: Self) -> u128 {
+                    end.wrapping_sub(begin) as $unsigned as u128
+                }
+
+                #[inline]
+                fn add_offset(self, offset: u128) -> Self {
+                    self.wrapping_add(offset as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_distinct_int!(
+    u8 => u8,
+    u16 => u16,
+    u32 => u32,
+    u64 => u64,
+    u128 => u128,
+    usize => usize,
+    i8 => u8,
+    i16 => u16,
+    i32 => u32,
+    i64 => u64,
+    i128 => u128,
+    isize => usize,
+);
+
+/// A trait to resolve open and exclusive range bounds for [`int_in_range_bounds`]
+pub trait BoundedInt: Int {
+    const MIN: Self;
+    const MAX: Self;
+
+    /// Returns `self + 1` or `None` if this overflows.
+    fn checked_increment(self) -> Option<Self>;
+
+    /// Returns `self - 1` or `None` if this overflows.
+    fn checked_decrement(self) -> Option<Self>;
+}
+
+macro_rules! impl_bounded_int {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl BoundedInt for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                #[inline]
+                fn checked_increment(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                #[inline]
+                fn checked_decrement(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_bounded_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 #[cfg(test)]
 mod tests {
+    use crate::RANDOMNESS1;
+
     use super::*;
 
     #[test]
@@ -244,6 +738,44 @@ mod tests {
         assert_eq!(result, 226364637901700064573816523306429827859);
     }
 
+    #[test]
+    fn try_int_in_range_works() {
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            assert_eq!(
+                try_int_in_range(subrand, 4, 18),
+                Ok(int_in_range(subrand, 4, 18))
+            );
+            assert_eq!(
+                try_int_in_range(subrand, -7i64, -7),
+                Ok(int_in_range(subrand, -7, -7))
+            );
+            assert_eq!(
+                try_int_in_range(subrand, 0u128, u128::MAX),
+                Ok(int_in_range(subrand, 0, u128::MAX))
+            );
+        }
+
+        assert_eq!(
+            try_int_in_range(RANDOMNESS1, 4, 3),
+            Err(RangeError::EmptyRange {
+                begin: "4".to_string(),
+                end: "3".to_string()
+            })
+        );
+        let err = try_int_in_range(RANDOMNESS1, i8::MAX, i8::MIN).unwrap_err();
+        assert_eq!(
+            err,
+            RangeError::EmptyRange {
+                begin: "127".to_string(),
+                end: "-128".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "cannot sample empty range: end (-128) is less than begin (127)"
+        );
+    }
+
     #[test]
     #[should_panic = "cannot sample empty range"]
     fn int_in_range_panicks_for_empty() {
@@ -258,6 +790,78 @@ mod tests {
         assert_eq!(result, 5);
     }
 
+    #[test]
+    fn int_in_range_bounds_works() {
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            // Inclusive ranges match int_in_range
+            assert_eq!(
+                int_in_range_bounds(subrand, 1..=6),
+                int_in_range(subrand, 1, 6)
+            );
+            // Exclusive upper bounds are mapped to inclusive ones
+            assert_eq!(
+                int_in_range_bounds(subrand, 0..10u32),
+                int_in_range(subrand, 0, 9)
+            );
+            assert_eq!(
+                int_in_range_bounds(subrand, -5..5i32),
+                int_in_range(subrand, -5, 4)
+            );
+            // Open bounds
+            assert_eq!(
+                int_in_range_bounds(subrand, 200u8..),
+                int_in_range(subrand, 200, u8::MAX)
+            );
+            assert_eq!(
+                int_in_range_bounds(subrand, ..3i8),
+                int_in_range(subrand, i8::MIN, 2)
+            );
+            assert_eq!(
+                int_in_range_bounds(subrand, ..=3i8),
+                int_in_range(subrand, i8::MIN, 3)
+            );
+            assert_eq!(
+                int_in_range_bounds::<u64, _>(subrand, ..),
+                int_in_range(subrand, u64::MIN, u64::MAX)
+            );
+            // Exclusive lower bound
+            assert_eq!(
+                int_in_range_bounds(subrand, (Bound::Excluded(4u16), Bound::Included(8u16))),
+                int_in_range(subrand, 5, 8)
+            );
+        }
+
+        // Single value ranges
+        assert_eq!(int_in_range_bounds(RANDOMNESS1, 7..8), 7);
+        assert_eq!(int_in_range_bounds(RANDOMNESS1, 7..=7), 7);
+        assert_eq!(int_in_range_bounds(RANDOMNESS1, u8::MAX..), u8::MAX);
+    }
+
+    #[test]
+    #[should_panic = "range must not be empty"]
+    fn int_in_range_bounds_panics_for_empty_exclusive_range() {
+        int_in_range_bounds(RANDOMNESS1, 5..5);
+    }
+
+    #[test]
+    #[should_panic = "range must not be empty"]
+    fn int_in_range_bounds_panics_for_reversed_range() {
+        #[allow(clippy::reversed_empty_ranges)]
+        int_in_range_bounds(RANDOMNESS1, 6..=1);
+    }
+
+    #[test]
+    #[should_panic = "range must not be empty"]
+    fn int_in_range_bounds_panics_for_exclusive_min() {
+        int_in_range_bounds(RANDOMNESS1, ..u32::MIN);
+    }
+
+    #[test]
+    #[should_panic = "range must not be empty"]
+    fn int_in_range_bounds_panics_for_excluded_max_start() {
+        int_in_range_bounds(RANDOMNESS1, (Bound::Excluded(i64::MAX), Bound::Unbounded));
+    }
+
     #[test]
     fn ints_in_range_works() {
         let randomness = [
@@ -288,4 +892,448 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn weighted_int_works() {
+        assert_eq!(weighted_int(RANDOMNESS1, &[1]).unwrap(), 0);
+
+        // Same index as the element selected by select_from_weighted
+        let elements = [('a', 12u32), ('b', 15), ('c', 8), ('d', 21)];
+        let weights: Vec<u32> = elements.iter().map(|(_, weight)| *weight).collect();
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            let index = weighted_int(subrand, &weights).unwrap();
+            let selected = crate::select_from_weighted(subrand, &elements).unwrap();
+            assert_eq!(elements[index].0, selected);
+        }
+    }
+
+    #[test]
+    fn weighted_int_fails_for_invalid_weights() {
+        let err = weighted_int(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = weighted_int(RANDOMNESS1, &[3, 0, 2]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+        let err = weighted_int(RANDOMNESS1, &[u32::MAX, 1]).unwrap_err();
+        assert_eq!(err, "Total weight is greater than maximum value of u32");
+    }
+
+    #[test]
+    fn weighted_int_distribution_matches_weights() {
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 200_000;
+        const ACCURACY: f32 = 0.03;
+        let weights = [50u32, 30, 15, 5];
+
+        let mut histogram = [0i32; 4];
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            histogram[weighted_int(subrand, &weights).unwrap()] += 1;
+        }
+
+        for (count, weight) in histogram.into_iter().zip(weights) {
+            let estimated = TEST_SAMPLE_SIZE as f32 * weight as f32 / 100.0;
+            let estimation_min = (estimated * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated * (1_f32 + ACCURACY)) as i32;
+            println!("{weight}: {count}, min: {estimation_min}, max: {estimation_max}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn assert_in_range_works() {
+        assert_in_range(5, 1, 6);
+        assert_in_range(1, 1, 6);
+        assert_in_range(6, 1, 6);
+        assert_in_range(-3i32, -5, 5);
+        assert_in_range(0u128, 0, 0);
+        assert_in_range(u64::MAX, 0, u64::MAX);
+
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(100) {
+            assert_in_range(int_in_range(randomness, 100u32, 200), 100, 200);
+        }
+    }
+
+    #[test]
+    #[should_panic = "value 7 is out of range [1, 6]"]
+    fn assert_in_range_panics_for_value_above_range() {
+        assert_in_range(7, 1, 6);
+    }
+
+    #[test]
+    #[should_panic = "value -6 is out of range [-5, 5]"]
+    fn assert_in_range_panics_for_value_below_range() {
+        assert_in_range(-6i64, -5, 5);
+    }
+
+    #[test]
+    fn random_coin_amount_works() {
+        let randomness = [
+            88, 85, 86, 91, 61, 64, 60, 71, 234, 24, 246, 200, 35, 73, 38, 187, 54, 59, 96, 9, 237,
+            27, 215, 103, 148, 230, 28, 48, 51, 114, 203, 219,
+        ];
+
+        let amount = random_coin_amount(randomness, Uint128::new(4), Uint128::new(18));
+        assert_eq!(amount, Uint128::new(int_in_range(randomness, 4u128, 18)));
+        assert!(amount >= Uint128::new(4) && amount <= Uint128::new(18));
+
+        // Single value range
+        let amount = random_coin_amount(randomness, Uint128::new(123), Uint128::new(123));
+        assert_eq!(amount, Uint128::new(123));
+
+        // Large values
+        let min = Uint128::MAX - Uint128::new(10);
+        let amount = random_coin_amount(randomness, min, Uint128::MAX);
+        assert!(amount >= min);
+        let amount = random_coin_amount(randomness, Uint128::zero(), Uint128::MAX);
+        assert_eq!(amount, Uint128::new(int_in_range(randomness, 0, u128::MAX)));
+
+        // Deterministic
+        assert_eq!(
+            random_coin_amount(randomness, Uint128::zero(), Uint128::MAX),
+            random_coin_amount(randomness, Uint128::zero(), Uint128::MAX)
+        );
+    }
+
+    #[test]
+    #[should_panic = "max must be greater than or equal to min"]
+    fn random_coin_amount_panics_for_max_less_than_min() {
+        random_coin_amount([0x77; 32], Uint128::new(5), Uint128::new(4));
+    }
+
+    #[test]
+    fn random_digits_works() {
+        let digits = random_digits(RANDOMNESS1, 10, 0);
+        assert!(digits.is_empty());
+
+        // Base 10
+        let digits = random_digits(RANDOMNESS1, 10, 40);
+        assert_eq!(digits.len(), 40);
+        assert!(digits.iter().all(|d| *d < 10));
+        assert_eq!(digits, ints_in_range(RANDOMNESS1, 40, 0u32, 9));
+
+        // Base 16
+        let digits = random_digits(RANDOMNESS1, 16, 40);
+        assert_eq!(digits.len(), 40);
+        assert!(digits.iter().all(|d| *d < 16));
+        assert!(digits.iter().any(|d| *d >= 10));
+
+        // Base 2
+        let digits = random_digits(RANDOMNESS1, 2, 40);
+        assert!(digits.iter().all(|d| *d < 2));
+    }
+
+    #[test]
+    #[should_panic = "base must be at least 2"]
+    fn random_digits_panics_for_base_1() {
+        random_digits(RANDOMNESS1, 1, 4);
+    }
+
+    #[test]
+    fn random_digits_distribution_is_uniform() {
+        const TEST_SAMPLE_SIZE: usize = 160_000;
+        const ACCURACY: f32 = 0.03;
+
+        let mut histogram = [0i32; 16];
+        for digit in random_digits(RANDOMNESS1, 16, TEST_SAMPLE_SIZE) {
+            histogram[digit as usize] += 1;
+        }
+
+        let estimated_count = (TEST_SAMPLE_SIZE / 16) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        println!("{histogram:?}");
+        for count in histogram {
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn distinct_ints_in_range_works() {
+        let randomness = [
+            88, 85, 86, 91, 61, 64, 60, 71, 234, 24, 246, 200, 35, 73, 38, 187, 54, 59, 96, 9, 237,
+            27, 215, 103, 148, 230, 28, 48, 51, 114, 203, 219,
+        ];
+
+        // Zero outputs
+        let result = distinct_ints_in_range(randomness, 0, 4, 18);
+        assert!(result.is_empty());
+
+        // Single element range
+        let result = distinct_ints_in_range(randomness, 1, 7u8, 7u8);
+        assert_eq!(result, [7]);
+
+        // Full range
+        let mut result = distinct_ints_in_range(randomness, 15, 4, 18);
+        result.sort();
+        assert_eq!(result, (4..=18).collect::<Vec<_>>());
+
+        // Large range, small count
+        let result = distinct_ints_in_range(randomness, 5, 0u64, u64::MAX);
+        assert_eq!(result.len(), 5);
+        assert_eq!(result.iter().collect::<BTreeSet<_>>().len(), 5);
+    }
+
+    #[test]
+    fn distinct_ints_in_range_works_for_signed() {
+        let randomness = [
+            74, 71, 86, 169, 247, 21, 60, 71, 234, 24, 246, 215, 35, 73, 38, 187, 54, 59, 96, 9,
+            237, 27, 215, 103, 14, 230, 28, 48, 51, 114, 203, 219,
+        ];
+
+        // -5..=5
+        let result = distinct_ints_in_range(randomness, 6, -5i32, 5);
+        assert_eq!(result.len(), 6);
+        assert_eq!(result.iter().collect::<BTreeSet<_>>().len(), 6);
+        assert!(result.iter().all(|n| (-5..=5).contains(n)));
+
+        let mut result = distinct_ints_in_range(randomness, 11, -5i64, 5);
+        result.sort();
+        assert_eq!(result, (-5..=5).collect::<Vec<_>>());
+
+        // Near-full i8 range
+        let result = distinct_ints_in_range(randomness, 255, -128i8, 126);
+        assert_eq!(result.iter().collect::<BTreeSet<_>>().len(), 255);
+        assert!(!result.contains(&127));
+
+        // Full i8 range
+        let mut result = distinct_ints_in_range(randomness, 256, i8::MIN, i8::MAX);
+        result.sort();
+        assert_eq!(result, (i8::MIN..=i8::MAX).collect::<Vec<_>>());
+
+        // Ranges that do not fit in the signed type
+        let result = distinct_ints_in_range(randomness, 10, i32::MIN, i32::MAX);
+        assert_eq!(result.iter().collect::<BTreeSet<_>>().len(), 10);
+        let result = distinct_ints_in_range(randomness, 10, i128::MIN, i128::MAX);
+        assert_eq!(result.iter().collect::<BTreeSet<_>>().len(), 10);
+    }
+
+    #[test]
+    #[should_panic = "attempt to draw more distinct integers than the range size"]
+    fn distinct_ints_in_range_panics_for_count_greater_than_range() {
+        let randomness = [0x77; 32];
+        let _ = distinct_ints_in_range(randomness, 12, -5, 5);
+    }
+
+    #[test]
+    #[should_panic = "cannot sample empty range"]
+    fn distinct_ints_in_range_panics_for_empty() {
+        let randomness = [0x77; 32];
+        let _ = distinct_ints_in_range(randomness, 0, 4, 3);
+    }
+
+    #[test]
+    fn distinct_ints_in_range_distribution_is_uniform() {
+        /// Draws 3 distinct values from 1..=9 many times and checks
+        /// that every value occurs at every output position with the same frequency
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 180_000;
+        const ACCURACY: f32 = 0.03;
+
+        let mut histograms = [[0i32; 9]; 3];
+        for subrand in sub_randomness([0x9C; 32]).take(TEST_SAMPLE_SIZE) {
+            let values = distinct_ints_in_range(subrand, 3, 1usize, 9);
+            for (position, value) in values.into_iter().enumerate() {
+                histograms[position][value - 1] += 1;
+            }
+        }
+
+        let estimated_count = (TEST_SAMPLE_SIZE / 9) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        for histogram in histograms {
+            println!("{histogram:?}");
+            for count in histogram {
+                assert!(count >= estimation_min && count <= estimation_max);
+            }
+        }
+    }
+
+    #[test]
+    fn distinct_ints_in_range_combinations_are_uniform() {
+        /// Draws 2 distinct values from 1..=5 many times and checks that each of the
+        /// 10 combinations occurs with the same frequency, independent of the order
+        use crate::sub_randomness::sub_randomness;
+        use std::collections::HashMap;
+
+        const TEST_SAMPLE_SIZE: usize = 100_000;
+        const ACCURACY: f32 = 0.04;
+
+        let mut histogram = HashMap::<(u8, u8), i32>::new();
+        for subrand in sub_randomness([0x4E; 32]).take(TEST_SAMPLE_SIZE) {
+            let values = distinct_ints_in_range(subrand, 2, 1u8, 5);
+            let combination = (values[0].min(values[1]), values[0].max(values[1]));
+            *histogram.entry(combination).or_default() += 1;
+        }
+        assert_eq!(histogram.len(), 10);
+
+        let estimated_count = (TEST_SAMPLE_SIZE / 10) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        for (combination, count) in histogram {
+            println!("{combination:?}: {count}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn gcd_works() {
+        assert_eq!(gcd(1, 1), 1);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(18, 12), 6);
+        assert_eq!(gcd(7, 13), 1);
+        assert_eq!(gcd(0, 5), 5);
+    }
+
+    #[test]
+    fn random_coprime_step_works() {
+        assert_eq!(random_coprime_step(RANDOMNESS1, 2), 1);
+
+        for n in [3u64, 10, 12, 30, 97, 360, 1024] {
+            for randomness in crate::sub_randomness(RANDOMNESS1).take(10) {
+                let step = random_coprime_step(randomness, n);
+                assert!(step >= 1 && step < n);
+                assert_eq!(gcd(step, n), 1);
+
+                // Full cycle
+                let mut visited = vec![false; n as usize];
+                let mut position = 0;
+                for _ in 0..n {
+                    assert!(!visited[position as usize]);
+                    visited[position as usize] = true;
+                    position = (position + step) % n;
+                }
+                assert_eq!(position, 0);
+            }
+        }
+
+        // Large n
+        let step = random_coprime_step(RANDOMNESS1, u64::MAX);
+        assert_eq!(gcd(step, u64::MAX), 1);
+    }
+
+    #[test]
+    #[should_panic = "n must be at least 2"]
+    fn random_coprime_step_panics_for_n_less_than_2() {
+        random_coprime_step(RANDOMNESS1, 1);
+    }
+
+    #[test]
+    fn has_modulo_bias_works() {
+        // Unbiased
+        assert!(!has_modulo_bias(1, 0));
+        assert!(!has_modulo_bias(1, 8));
+        assert!(!has_modulo_bias(2, 8));
+        assert!(!has_modulo_bias(256, 8));
+        assert!(!has_modulo_bias(1 << 32, 64));
+        assert!(!has_modulo_bias(1 << 63, 64));
+        assert!(!has_modulo_bias(1 << 63, 256));
+
+        // Biased
+        assert!(has_modulo_bias(3, 8));
+        assert!(has_modulo_bias(6, 8));
+        assert!(has_modulo_bias(100, 64));
+        assert!(has_modulo_bias(u64::MAX, 64));
+        assert!(has_modulo_bias(u64::MAX, 256));
+        // Range larger than the source
+        assert!(has_modulo_bias(512, 8));
+        assert!(has_modulo_bias(2, 0));
+
+        // Compare with 2^bits % n for small values
+        for bits in 0..16u32 {
+            for n in 1..300u64 {
+                let remainder = (1u64 << bits) % n;
+                assert_eq!(has_modulo_bias(n, bits), remainder != 0);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "range size must not be 0"]
+    fn has_modulo_bias_panics_for_zero_range() {
+        has_modulo_bias(0, 8);
+    }
+
+    #[test]
+    fn expected_distinct_works() {
+        fn assert_close(actual: f64, expected: f64) {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+
+        assert_close(expected_distinct(0, 10), 0.0);
+        assert_close(expected_distinct(1, 10), 1.0);
+        assert_close(expected_distinct(5, 1), 1.0);
+        // 2 coins: 1 + 1/2
+        assert_close(expected_distinct(2, 2), 1.5);
+        // 3 coins: 2 - 2/8
+        assert_close(expected_distinct(3, 2), 1.75);
+        // 2 dice: 1 + 5/6
+        assert_close(expected_distinct(2, 6), 11.0 / 6.0);
+        // 6 dice: 6 * (1 - (5/6)^6)
+        assert_close(
+            expected_distinct(6, 6),
+            6.0 * (1.0 - (5.0f64 / 6.0).powi(6)),
+        );
+
+        // Approaches the count for huge ranges and the range size for huge counts
+        assert!((expected_distinct(1_000, u64::MAX) - 1_000.0).abs() < 1e-6);
+        assert!((expected_distinct(10_000, 10) - 10.0).abs() < 1e-9);
+
+        // Matches the simulation
+        let values = ints_in_range(RANDOMNESS1, 100, 1u32, 100);
+        let distinct = values.iter().collect::<BTreeSet<_>>().len() as f64;
+        assert!((distinct - expected_distinct(100, 100)).abs() < 10.0);
+    }
+
+    #[test]
+    #[should_panic = "range size must not be 0"]
+    fn expected_distinct_panics_for_empty_range() {
+        expected_distinct(1, 0);
+    }
+
+    #[test]
+    fn bits_needed_works() {
+        // Powers of two
+        assert_eq!(bits_needed(1), 0);
+        assert_eq!(bits_needed(2), 1);
+        assert_eq!(bits_needed(4), 2);
+        assert_eq!(bits_needed(256), 8);
+        assert_eq!(bits_needed(1 << 63), 63);
+
+        // Non-powers of two
+        assert_eq!(bits_needed(3), 2);
+        assert_eq!(bits_needed(6), 3);
+        assert_eq!(bits_needed(100), 7);
+        assert_eq!(bits_needed(257), 9);
+        assert_eq!(bits_needed((1 << 63) + 1), 64);
+        assert_eq!(bits_needed(u64::MAX), 64);
+    }
+
+    #[test]
+    #[should_panic = "number of outcomes must not be 0"]
+    fn bits_needed_panics_for_0() {
+        bits_needed(0);
+    }
+
+    #[test]
+    fn outcomes_from_bits_works() {
+        assert_eq!(outcomes_from_bits(0), 1);
+        assert_eq!(outcomes_from_bits(1), 2);
+        assert_eq!(outcomes_from_bits(8), 256);
+        assert_eq!(outcomes_from_bits(63), 1 << 63);
+
+        // Roundtrip for powers of two
+        for bits in 0..64 {
+            assert_eq!(bits_needed(outcomes_from_bits(bits)), bits);
+        }
+        // Non-powers of two round up
+        assert_eq!(outcomes_from_bits(bits_needed(6)), 8);
+        assert_eq!(outcomes_from_bits(bits_needed(100)), 128);
+    }
+
+    #[test]
+    #[should_panic = "number of outcomes exceeds the u64 range"]
+    fn outcomes_from_bits_panics_for_64() {
+        outcomes_from_bits(64);
+    }
 }
@@ -5,31 +5,92 @@
 //! * Integrate your app with the nois proxy.
 //! * Safely transform and manipulate your randomness.
 
+mod bytes;
+mod cards;
 mod coinflip;
 mod decimal;
 mod dice;
+mod distributions;
 mod encoding;
 mod integers;
 mod js;
 mod pick;
 mod prng;
 mod proxy;
+mod recipe;
 mod select_from_weighted;
 mod shuffle;
 mod simulator;
+#[cfg(test)]
+mod stability;
+mod strings;
+mod structures;
 mod sub_randomness;
+mod teams;
+mod testing;
+mod time;
+mod timing;
+mod tournament;
+mod turns;
+mod visual;
 
-pub use coinflip::{coinflip, Side};
+pub use bytes::{permute_bytes, random_nonce, unpermute_bytes};
+pub use cards::{deal_poker_hand, evaluate_hand, Card, HandRank, Rank, Suit};
+pub use coinflip::{biased_toggle, coinflip, coinflip_biased, coinflips, FairCoin, Side};
 pub use decimal::random_decimal;
-pub use dice::roll_dice;
+pub use dice::{
+    initiative_order, roll_dice, roll_dice_sum, roll_mixed_pool, skill_check, CheckResult,
+    MAX_POOL_DICE,
+};
+pub use distributions::{
+    random_cell, random_triangular, random_walk, random_wall_grid, sample_pmf,
+};
 pub use encoding::{randomness_from_str, RandomnessFromStrErr};
-pub use integers::{int_in_range, ints_in_range, Int};
-pub use pick::pick;
-pub use proxy::{NoisCallback, ProxyExecuteMsg, ReceiverExecuteMsg, MAX_JOB_ID_LEN};
-pub use select_from_weighted::select_from_weighted;
-pub use shuffle::shuffle;
-pub use simulator::randomness_simulator;
-pub use sub_randomness::{sub_randomness, sub_randomness_with_key, SubRandomnessProvider};
+pub use integers::{
+    assert_in_range, bits_needed, distinct_ints_in_range, expected_distinct, has_modulo_bias,
+    int_in_range, int_in_range_bounds, ints_in_range, outcomes_from_bits, random_coin_amount,
+    random_coprime_step, random_digits, try_int_in_range, weighted_int, Int, RangeError,
+};
+pub use pick::{
+    pick, pick_cloned, pick_from_iter, pick_indices, pick_one_and_rest, pick_one_indexed,
+    pick_one_owned, pick_ordered, pick_probability, pick_winners_and_backups,
+    pick_with_replacement, random_order_statistic, try_pick, PickError,
+};
+pub use proxy::{
+    InvalidRandomnessLen, NoisCallback, ProxyExecuteMsg, ReceiverExecuteMsg, MAX_JOB_ID_LEN,
+};
+pub use recipe::Recipe;
+pub use select_from_weighted::{
+    choose_branch, draw_winner_with_proof, random_discount, random_subset_in_weight_band,
+    random_weights_summing_to, select_and_report, select_and_reweight, select_by_stake_seconds,
+    select_coin, select_excluding_previous, select_from_percentages, select_from_weighted,
+    select_from_weighted_excluding, select_index_from_weighted_with_tiebreak, select_trait,
+    spin_wheel, systematic_sample, verify_winner, was_selected, weighted_draws_with_floor,
+    CappedWeightedPool, LootEntry, LootTable, PityState, RoundRobinRandom, WinnerProof,
+    MAX_LOOT_TABLE_DEPTH,
+};
+pub use shuffle::{
+    break_ties, deterministic_order, shuffle, shuffle_in_place, shuffle_pinned, unshuffle,
+    verify_shuffle, FeistelPermutation, ShuffleCursor,
+};
+pub use simulator::{randomness_simulator, randomness_simulator_at_height};
+pub use strings::{encode_base, random_handle};
+pub use structures::{random_latin_square, random_tree};
+#[cfg(feature = "guard")]
+pub use sub_randomness::RandomnessGuard;
+pub use sub_randomness::{
+    antithetic_decimals, antithetic_pair, randomness_stream, randomness_stream_from_u64,
+    rendezvous_order, rng_for, seed_for_path, seeds_for_n, sub_randomness, sub_randomness_with_key,
+    trait_seeds, SubRandomnessProvider,
+};
+pub use teams::balance_teams;
+#[cfg(feature = "testing")]
+pub use testing::{simulate_distribution, test_randomness};
+pub use time::random_schedule;
+pub use timing::random_jitter;
+pub use tournament::{seed_bracket, seed_bracket_with_byes};
+pub use turns::starting_player;
+pub use visual::random_palette;
 
 #[cfg(test)]
 const RANDOMNESS1: [u8; 32] = [
@@ -1,6 +1,10 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use cosmwasm_std::Decimal;
 use rand::Rng;
 
-use crate::prng::make_prng;
+use crate::{int_in_range, ints_in_range, prng::make_prng, sub_randomness};
 
 /// Picks `n` elements from a given list.
 ///
@@ -41,9 +45,54 @@ use crate::prng::make_prng;
 /// assert_eq!(picked.len(), 2);
 /// assert_eq!(picked, vec!["su".to_string(), "bob".to_string()]);
 /// ```
-pub fn pick<T>(randomness: [u8; 32], n: usize, mut data: Vec<T>) -> Vec<T> {
+///
+/// Panics if `n` is greater than the input length. Use [`try_pick`] to handle this case
+/// as an error.
+pub fn pick<T>(randomness: [u8; 32], n: usize, data: Vec<T>) -> Vec<T> {
+    try_pick(randomness, n, data).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// The error returned by [`try_pick`] when more elements are requested than available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PickError {
+    /// The requested number of elements
+    pub n: usize,
+    /// The input length
+    pub len: usize,
+}
+
+impl fmt::Display for PickError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "attempt to pick more elements than the input length ({} > {})",
+            self.n, self.len
+        )
+    }
+}
+
+/// Picks `n` elements from a given list like [`pick`] but returns an error instead of
+/// panicking if `n` is greater than the input length.
+///
+/// This allows contracts to validate a user-supplied `n` without aborting the transaction.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick, try_pick, PickError};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let data = vec![1, 2, 3, 4];
+/// let picked = try_pick(randomness, 2, data.clone()).unwrap();
+/// assert_eq!(picked, pick(randomness, 2, data.clone()));
+///
+/// let err = try_pick(randomness, 5, data).unwrap_err();
+/// assert_eq!(err, PickError { n: 5, len: 4 });
+/// ```
+pub fn try_pick<T>(randomness: [u8; 32], n: usize, mut data: Vec<T>) -> Result<Vec<T>, PickError> {
     if n > data.len() {
-        panic!("attempt to pick more elements than the input length");
+        return Err(PickError { n, len: data.len() });
     }
     let mut rng = make_prng(randomness);
     for i in ((data.len() - n)..data.len()).rev() {
@@ -52,7 +101,371 @@ pub fn pick<T>(randomness: [u8; 32], n: usize, mut data: Vec<T>) -> Vec<T> {
     }
 
     // Get last n elements
-    data.split_off(data.len() - n)
+    Ok(data.split_off(data.len() - n))
+}
+
+/// Picks `n` distinct indices in \[0, len) without needing the elements themselves,
+/// e.g. when the data lives in contract storage.
+///
+/// The result is the same as `pick(randomness, n, (0..len).collect())`, i.e. the indices
+/// of the elements that [`pick`] selects, in the same order. Only the `n` swapped positions
+/// are tracked, such that the cost is independent of `len`.
+///
+/// Panics if `n` is greater than `len`.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick, pick_indices};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let indices = pick_indices(randomness, 6, 49);
+/// let numbers = pick(randomness, 6, (1..=49).collect());
+/// for (index, number) in indices.iter().zip(numbers) {
+///     assert_eq!(index + 1, number);
+/// }
+/// ```
+pub fn pick_indices(randomness: [u8; 32], n: usize, len: usize) -> Vec<usize> {
+    if n > len {
+        panic!("attempt to pick more elements than the input length");
+    }
+    // Fisher-Yates on the virtual list 0..len, storing only positions that differ from
+    // their index. Same draws as in `pick`.
+    let mut swapped = BTreeMap::<usize, usize>::new();
+    let mut rng = make_prng(randomness);
+    for i in ((len - n)..len).rev() {
+        let j = rng.gen_range(0..=i);
+        let value_i = swapped.get(&i).copied().unwrap_or(i);
+        let value_j = swapped.get(&j).copied().unwrap_or(j);
+        swapped.insert(i, value_j);
+        swapped.insert(j, value_i);
+    }
+
+    ((len - n)..len)
+        .map(|position| swapped.get(&position).copied().unwrap_or(position))
+        .collect()
+}
+
+/// Picks `n` elements from a given slice without consuming it.
+///
+/// Only the `n` selected elements are cloned, which is cheaper than cloning the whole list
+/// for [`pick`] when `n` is small. The result is the same as the result of [`pick`] for the
+/// same randomness and data, including the order.
+///
+/// Panics if `n` is greater than the input length.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick, pick_cloned};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let participants = vec!["bob".to_string(), "mary".to_string(), "su".to_string(), "marc".to_string()];
+/// let winners = pick_cloned(randomness, 2, &participants);
+/// assert_eq!(winners, pick(randomness, 2, participants.clone()));
+/// assert_eq!(participants.len(), 4);
+/// ```
+pub fn pick_cloned<T: Clone>(randomness: [u8; 32], n: usize, data: &[T]) -> Vec<T> {
+    pick_indices(randomness, n, data.len())
+        .into_iter()
+        .map(|index| data[index].clone())
+        .collect()
+}
+
+/// Picks `n` elements from a given list with replacement, e.g. for bootstrap resampling.
+///
+/// Each of the `n` elements is drawn independently and uniformly from `data`, such that the
+/// same element can occur multiple times and `n` may exceed the input length. The indices
+/// are the values of `ints_in_range(randomness, n, 0, data.len() - 1)`.
+///
+/// Panics if `data` is empty and `n` is greater than 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_with_replacement};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let samples = [3.2, 4.1, 2.8, 5.0];
+/// let resampled = pick_with_replacement(randomness, 10, &samples);
+/// assert_eq!(resampled.len(), 10);
+/// let mean = resampled.iter().sum::<f64>() / resampled.len() as f64;
+/// assert!(mean >= 2.8 && mean <= 5.0);
+/// ```
+pub fn pick_with_replacement<T: Clone>(randomness: [u8; 32], n: usize, data: &[T]) -> Vec<T> {
+    if n == 0 {
+        return Vec::new();
+    }
+    if data.is_empty() {
+        panic!("attempt to pick from an empty input");
+    }
+    ints_in_range(randomness, n, 0, data.len() - 1)
+        .into_iter()
+        .map(|index| data[index].clone())
+        .collect()
+}
+
+/// Picks `n` elements from a given list and returns them in their original order,
+/// e.g. for a leaderboard where picked rows should still read top to bottom.
+///
+/// The selected set is the same as the one of [`pick`] for the same randomness. Only the
+/// order differs: `pick` returns the elements in shuffled order.
+///
+/// Panics if `n` is greater than the input length.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick, pick_ordered};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let data: Vec<u32> = (1..=49).collect();
+/// let picked = pick_ordered(randomness, 6, data.clone());
+/// assert_eq!(picked, vec![7, 8, 10, 18, 22, 33]);
+///
+/// let mut shuffled = pick(randomness, 6, data);
+/// shuffled.sort();
+/// assert_eq!(shuffled, picked);
+/// ```
+pub fn pick_ordered<T>(randomness: [u8; 32], n: usize, data: Vec<T>) -> Vec<T> {
+    let mut indices = pick_indices(randomness, n, data.len());
+    indices.sort_unstable();
+    let mut indices = indices.into_iter().peekable();
+    data.into_iter()
+        .enumerate()
+        .filter_map(|(index, element)| {
+            if indices.peek() == Some(&index) {
+                indices.next();
+                Some(element)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Picks `n` elements from an iterator of unknown length using reservoir sampling
+/// (Algorithm R), e.g. to draw winners from a large number of ticket entries.
+///
+/// Only `n` elements are kept in memory at any time. Every element has the same probability
+/// of being picked. If the iterator yields `n` or fewer elements, all of them are returned
+/// in iteration order. The result is deterministic for a given randomness and input but
+/// differs from the result of [`pick`].
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_from_iter};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let tickets = (1..=50_000u32).map(|id| format!("ticket {id}"));
+/// let winners = pick_from_iter(randomness, 3, tickets);
+/// assert_eq!(winners.len(), 3);
+/// ```
+pub fn pick_from_iter<T, I: Iterator<Item = T>>(randomness: [u8; 32], n: usize, iter: I) -> Vec<T> {
+    let mut rng = make_prng(randomness);
+    let mut reservoir = Vec::with_capacity(n);
+    for (index, element) in iter.enumerate() {
+        if index < n {
+            reservoir.push(element);
+        } else {
+            let slot = rng.gen_range(0..=index);
+            if slot < n {
+                reservoir[slot] = element;
+            }
+        }
+    }
+    reservoir
+}
+
+/// Picks one element from a given list without shuffling it.
+///
+/// The selected element is moved to the end of the list and popped, such that the
+/// cost is independent of the list length. The index of the selected element is
+/// `int_in_range(randomness, 0, data.len() - 1)`.
+///
+/// Returns `None` for an empty list.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_one_owned};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let data = vec!["bob".to_string(), "mary".to_string(), "su".to_string()];
+/// let winner = pick_one_owned(randomness, data).unwrap();
+/// assert_eq!(winner, "bob");
+/// ```
+pub fn pick_one_owned<T>(randomness: [u8; 32], mut data: Vec<T>) -> Option<T> {
+    if data.is_empty() {
+        return None;
+    }
+    let index = int_in_range(randomness, 0, data.len() - 1);
+    Some(data.swap_remove(index))
+}
+
+/// Picks one element from a given list and returns it together with the remaining elements.
+///
+/// This works like [`pick_one_owned`], i.e. the selected element is swap-removed and the
+/// order of the remaining elements changes. The remaining list can be used directly for the
+/// next draw, e.g. in an iterative drawing loop.
+///
+/// Returns `None` for an empty list.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_one_and_rest, sub_randomness};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+/// let mut provider = sub_randomness(randomness);
+///
+/// let data = vec!["bob", "mary", "su"];
+/// let (first, rest) = pick_one_and_rest(provider.provide(), data).unwrap();
+/// let (second, rest) = pick_one_and_rest(provider.provide(), rest).unwrap();
+/// assert_ne!(first, second);
+/// assert_eq!(rest.len(), 1);
+/// ```
+pub fn pick_one_and_rest<T>(randomness: [u8; 32], mut data: Vec<T>) -> Option<(T, Vec<T>)> {
+    if data.is_empty() {
+        return None;
+    }
+    let index = int_in_range(randomness, 0, data.len() - 1);
+    let picked = data.swap_remove(index);
+    Some((picked, data))
+}
+
+/// Picks one element from a given slice and returns its index together with a reference to it.
+///
+/// The index is `int_in_range(randomness, 0, data.len() - 1)`, i.e. the same element as
+/// [`pick_one_owned`] selects. The input is not modified.
+///
+/// Returns `None` for an empty slice.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_one_indexed};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let data = ["bob", "mary", "su"];
+/// let (index, winner) = pick_one_indexed(randomness, &data).unwrap();
+/// assert_eq!(data[index], *winner);
+/// ```
+pub fn pick_one_indexed<T>(randomness: [u8; 32], data: &[T]) -> Option<(usize, &T)> {
+    if data.is_empty() {
+        return None;
+    }
+    let index = int_in_range(randomness, 0, data.len() - 1);
+    Some((index, &data[index]))
+}
+
+/// Picks `winners` winners and `backups` runners-up from a given list in one draw.
+///
+/// All `winners + backups` elements are distinct. They are drawn in a single shuffle-based
+/// pass and split in draw order, i.e. the first `winners` drawn elements are the winners
+/// and the following `backups` drawn elements are the runners-up.
+///
+/// Returns an error if `winners + backups` exceeds the input length.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, pick_winners_and_backups};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let participants = vec!["bob", "mary", "su", "marc", "jo", "lin"];
+/// let (winners, backups) = pick_winners_and_backups(randomness, participants, 2, 3).unwrap();
+/// assert_eq!(winners.len(), 2);
+/// assert_eq!(backups.len(), 3);
+/// assert!(winners.iter().all(|winner| !backups.contains(winner)));
+/// ```
+pub fn pick_winners_and_backups<T>(
+    randomness: [u8; 32],
+    data: Vec<T>,
+    winners: usize,
+    backups: usize,
+) -> Result<(Vec<T>, Vec<T>), String> {
+    let total = winners
+        .checked_add(backups)
+        .filter(|total| *total <= data.len())
+        .ok_or_else(|| String::from("Number of winners and backups exceeds the input length"))?;
+
+    // `pick` moves the n-th drawn element to position `total - n`, so reverse to get draw order
+    let mut drawn = pick(randomness, total, data);
+    drawn.reverse();
+    let backups = drawn.split_off(winners);
+    Ok((drawn, backups))
+}
+
+/// Returns the element with the `k`-th smallest random score (0-based), e.g. to pick the
+/// player with median luck.
+///
+/// Every element gets a score from the sub-randomness stream of `randomness`, in the order
+/// of `data`. `k = 0` returns the element with the smallest score and `k = data.len() - 1`
+/// the one with the largest score.
+///
+/// Returns an error if `k` is not less than the input length.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_order_statistic};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let players = ["bob", "mary", "su", "marc", "jo"];
+/// let median = random_order_statistic(randomness, &players, players.len() / 2).unwrap();
+/// assert!(players.contains(median));
+/// ```
+pub fn random_order_statistic<T>(randomness: [u8; 32], data: &[T], k: usize) -> Result<&T, String> {
+    if k >= data.len() {
+        return Err(String::from("Rank must be less than the input length"));
+    }
+
+    // Ties in the 256 bit scores are practically impossible but broken by index
+    let mut scored: Vec<([u8; 32], usize)> = sub_randomness(randomness)
+        .take(data.len())
+        .enumerate()
+        .map(|(index, score)| (score, index))
+        .collect();
+    let (_, (_, index), _) = scored.select_nth_unstable(k);
+    Ok(&data[*index])
+}
+
+/// Returns the probability that a given element is contained in the result of
+/// [`pick`] when picking `n` out of `len` elements.
+///
+/// Since [`pick`] is uniform, this is `n/len` for every element.
+///
+/// Panics if `len` is 0 or `n` is greater than `len`.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::pick_probability;
+///
+/// // Pick 6 out of 49
+/// let probability = pick_probability(49, 6);
+/// assert_eq!(probability, Decimal::from_ratio(6u32, 49u32));
+/// ```
+pub fn pick_probability(len: usize, n: usize) -> Decimal {
+    if len == 0 {
+        panic!("input length must not be 0");
+    }
+    if n > len {
+        panic!("attempt to pick more elements than the input length");
+    }
+    Decimal::from_ratio(n as u128, len as u128)
 }
 
 #[cfg(test)]
@@ -101,6 +514,158 @@ mod tests {
         let _picked = pick(RANDOMNESS1, 5, data);
     }
 
+    #[test]
+    fn pick_one_owned_works() {
+        assert_eq!(pick_one_owned(RANDOMNESS1, Vec::<u32>::new()), None);
+        assert_eq!(pick_one_owned(RANDOMNESS1, vec![5]), Some(5));
+
+        // Same index as int_in_range
+        let data: Vec<usize> = (0..17).collect();
+        let index = int_in_range(RANDOMNESS1, 0, data.len() - 1);
+        assert_eq!(pick_one_owned(RANDOMNESS1, data), Some(index));
+
+        // Element type is neither Copy nor Clone
+        #[derive(PartialEq, Debug)]
+        struct Continent(String);
+        let data = vec![
+            Continent("Africa".into()),
+            Continent("America".to_string()),
+            Continent("Antarctica".to_string()),
+        ];
+        let index = int_in_range(RANDOMNESS1, 0, 2);
+        let expected = ["Africa", "America", "Antarctica"][index];
+        assert_eq!(
+            pick_one_owned(RANDOMNESS1, data),
+            Some(Continent(expected.to_string()))
+        );
+    }
+
+    #[test]
+    fn pick_one_owned_distribution_is_uniform() {
+        /// Picks one of 6 elements many times and checks
+        /// that every element is picked with the same frequency within 2%
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 120_000;
+        const ACCURACY: f32 = 0.02;
+
+        let data = vec![0usize, 1, 2, 3, 4, 5];
+        let mut histogram = [0i32; 6];
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            histogram[pick_one_owned(subrand, data.clone()).unwrap()] += 1;
+        }
+
+        let estimated_count = (TEST_SAMPLE_SIZE / data.len()) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as i32;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as i32;
+        for (bin, count) in histogram.into_iter().enumerate() {
+            println!("{bin}: {count}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn pick_one_and_rest_works() {
+        let data: Vec<u32> = vec![];
+        assert_eq!(pick_one_and_rest(RANDOMNESS1, data), None);
+
+        let (picked, rest) = pick_one_and_rest(RANDOMNESS1, vec![5]).unwrap();
+        assert_eq!(picked, 5);
+        assert_eq!(rest, Vec::<u32>::new());
+
+        // Same element as pick_one_owned
+        let data = vec!["a", "b", "c", "d"];
+        let (picked, rest) = pick_one_and_rest(RANDOMNESS1, data.clone()).unwrap();
+        assert_eq!(Some(picked), pick_one_owned(RANDOMNESS1, data));
+        assert_eq!(rest.len(), 3);
+        assert!(!rest.contains(&picked));
+
+        // Drain one element at a time
+        let mut provider = crate::sub_randomness(RANDOMNESS1);
+        let mut rest: Vec<u32> = (1..=30).collect();
+        let mut drawn = vec![];
+        while let Some((picked, remaining)) = pick_one_and_rest(provider.provide(), rest) {
+            assert!(!drawn.contains(&picked));
+            drawn.push(picked);
+            rest = remaining;
+        }
+        assert_eq!(drawn.len(), 30);
+        drawn.sort();
+        assert_eq!(drawn, (1..=30).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn pick_winners_and_backups_works() {
+        let data: Vec<u32> = (1..=20).collect();
+        let (winners, backups) = pick_winners_and_backups(RANDOMNESS1, data.clone(), 3, 5).unwrap();
+        assert_eq!(winners.len(), 3);
+        assert_eq!(backups.len(), 5);
+        for winner in &winners {
+            assert!(!backups.contains(winner));
+        }
+        let mut all: Vec<u32> = winners.iter().chain(backups.iter()).copied().collect();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), 8);
+        assert!(all.iter().all(|e| data.contains(e)));
+
+        // Same elements as pick, in draw order
+        let mut picked = pick(RANDOMNESS1, 8, data.clone());
+        picked.reverse();
+        assert_eq!(&picked[..3], winners.as_slice());
+        assert_eq!(&picked[3..], backups.as_slice());
+
+        // Use all elements
+        let (winners, backups) =
+            pick_winners_and_backups(RANDOMNESS1, data.clone(), 15, 5).unwrap();
+        assert_eq!(winners.len(), 15);
+        assert_eq!(backups.len(), 5);
+
+        // No backups
+        let (winners, backups) = pick_winners_and_backups(RANDOMNESS1, data.clone(), 2, 0).unwrap();
+        assert_eq!(winners.len(), 2);
+        assert_eq!(backups, Vec::<u32>::new());
+
+        // Too many
+        let err = pick_winners_and_backups(RANDOMNESS1, data.clone(), 15, 6).unwrap_err();
+        assert_eq!(
+            err,
+            "Number of winners and backups exceeds the input length"
+        );
+        let err = pick_winners_and_backups(RANDOMNESS1, data, usize::MAX, 1).unwrap_err();
+        assert_eq!(
+            err,
+            "Number of winners and backups exceeds the input length"
+        );
+    }
+
+    #[test]
+    fn pick_probability_works() {
+        use std::str::FromStr;
+
+        assert_eq!(pick_probability(1, 0), Decimal::zero());
+        assert_eq!(pick_probability(1, 1), Decimal::one());
+        assert_eq!(pick_probability(4, 2), Decimal::from_str("0.5").unwrap());
+        assert_eq!(pick_probability(10, 3), Decimal::from_str("0.3").unwrap());
+        assert_eq!(pick_probability(8, 8), Decimal::one());
+        assert_eq!(
+            pick_probability(3, 1),
+            Decimal::from_str("0.333333333333333333").unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic = "input length must not be 0"]
+    fn pick_probability_panics_for_empty_input() {
+        pick_probability(0, 0);
+    }
+
+    #[test]
+    #[should_panic = "attempt to pick more elements than the input length"]
+    fn pick_probability_panics_for_n_greater_than_len() {
+        pick_probability(4, 5);
+    }
+
     #[test]
     fn pick_distribution_is_uniform() {
         /// This test will generate a huge amount  of subrandomness and picks n elements from the list
@@ -174,4 +739,288 @@ mod tests {
         let shuffled = shuffle(RANDOMNESS1, data);
         assert_eq!(picked, shuffled);
     }
+
+    #[test]
+    fn random_order_statistic_works() {
+        let data = ["a", "b", "c", "d", "e", "f", "g"];
+
+        // k = 0 returns the element with the smallest score
+        let scores: Vec<[u8; 32]> = sub_randomness(RANDOMNESS1).take(data.len()).collect();
+        let min_index = (0..data.len()).min_by_key(|i| scores[*i]).unwrap();
+        let max_index = (0..data.len()).max_by_key(|i| scores[*i]).unwrap();
+        assert_eq!(
+            random_order_statistic(RANDOMNESS1, &data, 0).unwrap(),
+            &data[min_index]
+        );
+        assert_eq!(
+            random_order_statistic(RANDOMNESS1, &data, data.len() - 1).unwrap(),
+            &data[max_index]
+        );
+
+        // All ranks together cover all elements
+        let mut ranked: Vec<&str> = (0..data.len())
+            .map(|k| *random_order_statistic(RANDOMNESS1, &data, k).unwrap())
+            .collect();
+        ranked.sort();
+        assert_eq!(ranked, data);
+
+        // Reproducible
+        assert_eq!(
+            random_order_statistic(RANDOMNESS1, &data, 3),
+            random_order_statistic(RANDOMNESS1, &data, 3)
+        );
+
+        assert_eq!(random_order_statistic(RANDOMNESS1, &[42], 0), Ok(&42));
+    }
+
+    #[test]
+    fn random_order_statistic_fails_for_rank_out_of_bounds() {
+        let err = random_order_statistic(RANDOMNESS1, &[1, 2, 3], 3).unwrap_err();
+        assert_eq!(err, "Rank must be less than the input length");
+        let err = random_order_statistic::<u8>(RANDOMNESS1, &[], 0).unwrap_err();
+        assert_eq!(err, "Rank must be less than the input length");
+    }
+
+    #[test]
+    fn pick_indices_works() {
+        assert_eq!(pick_indices(RANDOMNESS1, 0, 0), Vec::<usize>::new());
+        assert_eq!(pick_indices(RANDOMNESS1, 0, 10), Vec::<usize>::new());
+        assert_eq!(pick_indices(RANDOMNESS1, 1, 1), vec![0]);
+
+        // Same order as pick
+        for subrand in sub_randomness(RANDOMNESS1).take(50) {
+            for (n, len) in [(1, 1), (3, 10), (6, 49), (10, 10), (5, 1000)] {
+                let indices = pick_indices(subrand, n, len);
+                let picked = pick(subrand, n, (0..len).collect());
+                assert_eq!(indices, picked);
+            }
+        }
+
+        // Large lengths are not materialized
+        let indices = pick_indices(RANDOMNESS1, 5, usize::MAX);
+        assert_eq!(indices.len(), 5);
+        for (i, index) in indices.iter().enumerate() {
+            assert!(!indices[..i].contains(index));
+        }
+    }
+
+    #[test]
+    #[should_panic = "attempt to pick more elements than the input length"]
+    fn pick_indices_panics_for_n_greater_than_len() {
+        pick_indices(RANDOMNESS1, 4, 3);
+    }
+
+    #[test]
+    fn try_pick_works() {
+        for subrand in sub_randomness(RANDOMNESS1).take(20) {
+            for n in 0..=5 {
+                let data = vec![1, 2, 3, 4, 5];
+                assert_eq!(
+                    try_pick(subrand, n, data.clone()),
+                    Ok(pick(subrand, n, data))
+                );
+            }
+        }
+
+        let err = try_pick(RANDOMNESS1, 4, vec![1, 2, 3]).unwrap_err();
+        assert_eq!(err, PickError { n: 4, len: 3 });
+        assert_eq!(
+            err.to_string(),
+            "attempt to pick more elements than the input length (4 > 3)"
+        );
+        let err = try_pick(RANDOMNESS1, 1, Vec::<u8>::new()).unwrap_err();
+        assert_eq!(err, PickError { n: 1, len: 0 });
+    }
+
+    #[test]
+    fn pick_from_iter_works() {
+        assert_eq!(pick_from_iter(RANDOMNESS1, 3, 0..0u32), Vec::<u32>::new());
+        assert_eq!(pick_from_iter(RANDOMNESS1, 0, 0..10u32), Vec::<u32>::new());
+
+        // Fewer elements than n
+        assert_eq!(pick_from_iter(RANDOMNESS1, 5, 0..3u32), vec![0, 1, 2]);
+        assert_eq!(pick_from_iter(RANDOMNESS1, 3, 0..3u32), vec![0, 1, 2]);
+
+        // Distinct elements of the input
+        let picked = pick_from_iter(RANDOMNESS1, 10, 0..1000u32);
+        assert_eq!(picked.len(), 10);
+        let mut sorted = picked.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 10);
+        assert!(sorted.iter().all(|v| *v < 1000));
+
+        // Deterministic
+        assert_eq!(pick_from_iter(RANDOMNESS1, 10, 0..1000u32), picked);
+        assert_ne!(pick_from_iter([0x77; 32], 10, 0..1000u32), picked);
+    }
+
+    #[test]
+    fn pick_from_iter_is_uniform() {
+        const TEST_SAMPLE_SIZE: usize = 100_000;
+        const ACCURACY: f32 = 0.03;
+        const LEN: usize = 10;
+        const N: usize = 3;
+        let mut histogram = [0usize; LEN];
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            for element in pick_from_iter(subrand, N, 0..LEN) {
+                histogram[element] += 1;
+            }
+        }
+        // Every element is picked with probability N/LEN
+        let expected = TEST_SAMPLE_SIZE * N / LEN;
+        let min = (expected as f32 * (1.0 - ACCURACY)) as usize;
+        let max = (expected as f32 * (1.0 + ACCURACY)) as usize;
+        for count in histogram {
+            assert!(count >= min && count <= max, "{histogram:?}");
+        }
+    }
+
+    #[test]
+    fn pick_cloned_works() {
+        assert_eq!(pick_cloned::<u8>(RANDOMNESS1, 0, &[]), Vec::<u8>::new());
+
+        let data: Vec<String> = (0..20).map(|i| format!("entry {i}")).collect();
+        for subrand in sub_randomness(RANDOMNESS1).take(50) {
+            for n in [0, 1, 5, 20] {
+                assert_eq!(
+                    pick_cloned(subrand, n, &data),
+                    pick(subrand, n, data.clone())
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "attempt to pick more elements than the input length"]
+    fn pick_cloned_panics_for_n_greater_than_len() {
+        pick_cloned(RANDOMNESS1, 4, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn pick_with_replacement_works() {
+        assert_eq!(
+            pick_with_replacement::<u8>(RANDOMNESS1, 0, &[]),
+            Vec::<u8>::new()
+        );
+        assert_eq!(pick_with_replacement(RANDOMNESS1, 3, &[7]), vec![7, 7, 7]);
+
+        let data = ["a", "b", "c"];
+        // More outputs than inputs
+        let picked = pick_with_replacement(RANDOMNESS1, 100, &data);
+        assert_eq!(picked.len(), 100);
+        assert!(picked.iter().all(|element| data.contains(element)));
+        // Elements repeat
+        assert!(data
+            .iter()
+            .all(|element| picked.iter().filter(|p| *p == element).count() > 1));
+
+        // Reproducible
+        assert_eq!(pick_with_replacement(RANDOMNESS1, 100, &data), picked);
+    }
+
+    #[test]
+    #[should_panic = "attempt to pick from an empty input"]
+    fn pick_with_replacement_panics_for_empty_input() {
+        pick_with_replacement::<u8>(RANDOMNESS1, 1, &[]);
+    }
+
+    #[test]
+    fn pick_with_replacement_distribution_is_uniform() {
+        const TEST_SAMPLE_SIZE: usize = 100_000;
+        const ACCURACY: f32 = 0.03;
+
+        let data = [0usize, 1, 2, 3, 4];
+        let mut histogram = [0usize; 5];
+        for element in pick_with_replacement(RANDOMNESS1, TEST_SAMPLE_SIZE, &data) {
+            histogram[element] += 1;
+        }
+
+        let estimated_count = (TEST_SAMPLE_SIZE / data.len()) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as usize;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as usize;
+        for count in histogram {
+            assert!(
+                count >= estimation_min && count <= estimation_max,
+                "{count}"
+            );
+        }
+    }
+
+    #[test]
+    fn pick_ordered_works() {
+        assert_eq!(
+            pick_ordered(RANDOMNESS1, 0, Vec::<u8>::new()),
+            Vec::<u8>::new()
+        );
+        assert_eq!(pick_ordered(RANDOMNESS1, 1, vec![5]), vec![5]);
+
+        let data = vec!["a", "b", "c", "d", "e", "f", "g", "h"];
+        for subrand in sub_randomness(RANDOMNESS1).take(50) {
+            for n in 0..=data.len() {
+                let picked = pick_ordered(subrand, n, data.clone());
+                assert_eq!(picked.len(), n);
+                // Original relative order
+                let positions: Vec<usize> = picked
+                    .iter()
+                    .map(|p| data.iter().position(|d| d == p).unwrap())
+                    .collect();
+                assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+                // Same set as pick
+                let mut shuffled = pick(subrand, n, data.clone());
+                shuffled.sort();
+                assert_eq!(shuffled, picked);
+            }
+        }
+
+        // Non-Clone elements
+        #[derive(Debug, PartialEq)]
+        struct Row(u32);
+        let rows: Vec<Row> = (0..10).map(Row).collect();
+        let picked = pick_ordered(RANDOMNESS1, 3, rows);
+        assert_eq!(picked.len(), 3);
+    }
+
+    #[test]
+    #[should_panic = "attempt to pick more elements than the input length"]
+    fn pick_ordered_panics_for_n_greater_than_len() {
+        pick_ordered(RANDOMNESS1, 4, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn pick_one_indexed_works() {
+        assert_eq!(pick_one_indexed::<u8>(RANDOMNESS1, &[]), None);
+        assert_eq!(pick_one_indexed(RANDOMNESS1, &[7]), Some((0, &7)));
+
+        let data = vec!["bob", "mary", "su", "marc"];
+        for subrand in sub_randomness(RANDOMNESS1).take(100) {
+            let (index, picked) = pick_one_indexed(subrand, &data).unwrap();
+            assert_eq!(index, int_in_range(subrand, 0, data.len() - 1));
+            assert_eq!(*picked, data[index]);
+            assert_eq!(pick_one_owned(subrand, data.clone()).as_ref(), Some(picked));
+        }
+    }
+
+    #[test]
+    fn pick_one_indexed_distribution_is_uniform() {
+        const TEST_SAMPLE_SIZE: usize = 100_000;
+        const ACCURACY: f32 = 0.03;
+
+        let data = [10, 20, 30, 40, 50];
+        let mut histogram = [0usize; 5];
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let (index, _) = pick_one_indexed(subrand, &data).unwrap();
+            histogram[index] += 1;
+        }
+
+        let estimated_count = (TEST_SAMPLE_SIZE / data.len()) as f32;
+        let estimation_min = (estimated_count * (1_f32 - ACCURACY)) as usize;
+        let estimation_max = (estimated_count * (1_f32 + ACCURACY)) as usize;
+        for count in histogram {
+            assert!(
+                count >= estimation_min && count <= estimation_max,
+                "{count}"
+            );
+        }
+    }
 }
@@ -1,6 +1,10 @@
+use std::fmt;
+
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{HexBinary, Timestamp};
 
+use crate::{sub_randomness_with_key, SubRandomnessProvider};
+
 /// Max length that the job ID is allowed to have (in bytes)
 ///
 /// The value is chosen to be enough for 32 byte hashes (such as sha256)
@@ -49,6 +53,70 @@ pub struct NoisCallback {
     pub randomness: HexBinary,
 }
 
+impl NoisCallback {
+    /// Returns a sub-randomness provider keyed by the job ID and an extra `key`.
+    ///
+    /// This allows multiple independent uses of a single callback, e.g. one stream for
+    /// selecting winners and one for assigning prizes. The job ID is length-prefixed such that
+    /// different combinations of job ID and key never lead to the same stream.
+    ///
+    /// Returns an error if the randomness is not 32 bytes long.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{HexBinary, Timestamp};
+    /// use nois::{int_in_range, NoisCallback};
+    ///
+    /// let callback = NoisCallback {
+    ///     job_id: "round 7".to_string(),
+    ///     published: Timestamp::from_seconds(1682086395),
+    ///     randomness: HexBinary::from_hex("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap(),
+    /// };
+    ///
+    /// let mut winners = callback.sub_randomness_with_key("winners").unwrap();
+    /// let mut prizes = callback.sub_randomness_with_key("prizes").unwrap();
+    /// let winner = int_in_range(winners.provide(), 0, 99);
+    /// let prize = int_in_range(prizes.provide(), 0, 2);
+    /// ```
+    pub fn sub_randomness_with_key(
+        &self,
+        key: impl AsRef<[u8]>,
+    ) -> Result<Box<SubRandomnessProvider>, InvalidRandomnessLen> {
+        let randomness: [u8; 32] =
+            self.randomness
+                .as_slice()
+                .try_into()
+                .map_err(|_| InvalidRandomnessLen {
+                    len: self.randomness.len(),
+                })?;
+        let job_id = self.job_id.as_bytes();
+        let key = key.as_ref();
+        let mut framed = Vec::with_capacity(8 + job_id.len() + key.len());
+        framed.extend_from_slice(&(job_id.len() as u64).to_be_bytes());
+        framed.extend_from_slice(job_id);
+        framed.extend_from_slice(key);
+        Ok(sub_randomness_with_key(randomness, framed))
+    }
+}
+
+/// The error returned when a randomness does not have the expected length of 32 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRandomnessLen {
+    /// Actual length in bytes
+    pub len: usize,
+}
+
+impl fmt::Display for InvalidRandomnessLen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected a randomness of 32 bytes but got {} bytes",
+            self.len
+        )
+    }
+}
+
 /// This is just a helper to properly serialize the above callback.
 /// The actual receiver should include this variant in the larger ExecuteMsg enum.
 #[cw_serde]
@@ -82,4 +150,55 @@ mod tests {
             br#"{"nois_receive":{"callback":{"job_id":"first","published":"1682086395000000000","randomness":"aabbccddaabbccddaabbccddaabbccddaabbccddaabbccddaabbccddaabbccdd"}}}"#
         );
     }
+
+    fn make_callback(job_id: &str) -> NoisCallback {
+        NoisCallback {
+            job_id: job_id.to_string(),
+            published: Timestamp::from_seconds(1682086395),
+            randomness: HexBinary::from_hex(
+                "aabbccddaabbccddaabbccddaabbccddaabbccddaabbccddaabbccddaabbccdd",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn nois_callback_sub_randomness_with_key_works() {
+        let callback = make_callback("job");
+
+        // Same key leads to the same stream
+        let mut provider1 = callback.sub_randomness_with_key("a").unwrap();
+        let mut provider2 = callback.sub_randomness_with_key("a").unwrap();
+        assert_eq!(provider1.provide(), provider2.provide());
+        assert_eq!(provider1.provide(), provider2.provide());
+
+        // Different keys lead to independent streams
+        let mut provider1 = callback.sub_randomness_with_key("a").unwrap();
+        let mut provider2 = callback.sub_randomness_with_key("b").unwrap();
+        let values1: Vec<[u8; 32]> = (0..5).map(|_| provider1.provide()).collect();
+        let values2: Vec<[u8; 32]> = (0..5).map(|_| provider2.provide()).collect();
+        for value in &values1 {
+            assert!(!values2.contains(value));
+        }
+
+        // Job ID and key are framed unambiguously
+        let mut provider1 = make_callback("ab").sub_randomness_with_key("c").unwrap();
+        let mut provider2 = make_callback("a").sub_randomness_with_key("bc").unwrap();
+        assert_ne!(provider1.provide(), provider2.provide());
+    }
+
+    #[test]
+    fn nois_callback_sub_randomness_with_key_fails_for_invalid_randomness() {
+        let mut callback = make_callback("job");
+        callback.randomness = HexBinary::from_hex("aabbccdd").unwrap();
+        let err = callback
+            .sub_randomness_with_key("a")
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(err, InvalidRandomnessLen { len: 4 });
+        assert_eq!(
+            err.to_string(),
+            "Expected a randomness of 32 bytes but got 4 bytes"
+        );
+    }
 }
@@ -0,0 +1,123 @@
+use rand::distributions::uniform::SampleUniform;
+
+use crate::{
+    coinflip, int_in_range, roll_dice, shuffle, sub_randomness, sub_randomness_with_key, Int, Side,
+    SubRandomnessProvider,
+};
+
+/// A helper for multi-step randomness recipes.
+///
+/// Every step consumes one sub-randomness from an internal [`SubRandomnessProvider`], such that
+/// the results of the individual steps are independent of each other by construction.
+///
+/// ## Example
+///
+/// Flip a coin to decide who starts, roll a dice and shuffle the deck:
+///
+/// ```
+/// use nois::{randomness_from_str, Recipe, Side};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut recipe = Recipe::new(randomness);
+/// let starter = match recipe.coinflip() {
+///     Side::Heads => "alice",
+///     Side::Tails => "bob",
+/// };
+/// let steps = recipe.roll_dice();
+/// let bonus = recipe.int_in_range(10, 20);
+/// let deck = recipe.shuffle(vec!["A", "K", "Q", "J"]);
+/// assert!(steps >= 1 && steps <= 6);
+/// assert!(bonus >= 10 && bonus <= 20);
+/// assert_eq!(deck.len(), 4);
+/// ```
+pub struct Recipe {
+    provider: Box<SubRandomnessProvider>,
+}
+
+impl Recipe {
+    /// Creates a recipe based on [`sub_randomness`].
+    pub fn new(randomness: [u8; 32]) -> Self {
+        Self {
+            provider: sub_randomness(randomness),
+        }
+    }
+
+    /// Creates a recipe based on [`sub_randomness_with_key`].
+    pub fn with_key(randomness: [u8; 32], key: impl AsRef<[u8]>) -> Self {
+        Self {
+            provider: sub_randomness_with_key(randomness, key),
+        }
+    }
+
+    /// Returns the next sub-randomness for steps not covered by the other methods.
+    pub fn next_randomness(&mut self) -> [u8; 32] {
+        self.provider.provide()
+    }
+
+    /// See [`coinflip`](crate::coinflip).
+    pub fn coinflip(&mut self) -> Side {
+        coinflip(self.provider.provide())
+    }
+
+    /// See [`roll_dice`](crate::roll_dice).
+    pub fn roll_dice(&mut self) -> u8 {
+        roll_dice(self.provider.provide())
+    }
+
+    /// See [`int_in_range`](crate::int_in_range).
+    pub fn int_in_range<T>(&mut self, begin: T, end: T) -> T
+    where
+        T: SampleUniform + Int,
+    {
+        int_in_range(self.provider.provide(), begin, end)
+    }
+
+    /// See [`shuffle`](crate::shuffle).
+    pub fn shuffle<T>(&mut self, data: Vec<T>) -> Vec<T> {
+        shuffle(self.provider.provide(), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn recipe_works() {
+        let mut recipe = Recipe::new(RANDOMNESS1);
+        let side = recipe.coinflip();
+        let dice = recipe.roll_dice();
+        let number = recipe.int_in_range(-50, 50);
+        let shuffled = recipe.shuffle(vec![1, 2, 3, 4, 5]);
+
+        // Every step uses the next sub-randomness
+        let mut provider = sub_randomness(RANDOMNESS1);
+        assert_eq!(side, coinflip(provider.provide()));
+        assert_eq!(dice, roll_dice(provider.provide()));
+        assert_eq!(number, int_in_range(provider.provide(), -50, 50));
+        assert_eq!(shuffled, shuffle(provider.provide(), vec![1, 2, 3, 4, 5]));
+        assert_eq!(recipe.next_randomness(), provider.provide());
+
+        // Reproducible
+        let mut recipe = Recipe::new(RANDOMNESS1);
+        assert_eq!(recipe.coinflip(), side);
+        assert_eq!(recipe.roll_dice(), dice);
+        assert_eq!(recipe.int_in_range(-50, 50), number);
+        assert_eq!(recipe.shuffle(vec![1, 2, 3, 4, 5]), shuffled);
+    }
+
+    #[test]
+    fn recipe_with_key_works() {
+        let mut recipe = Recipe::with_key(RANDOMNESS1, "round 1");
+        let mut provider = sub_randomness_with_key(RANDOMNESS1, "round 1");
+        assert_eq!(recipe.next_randomness(), provider.provide());
+        assert_eq!(recipe.roll_dice(), roll_dice(provider.provide()));
+
+        let mut recipe1 = Recipe::with_key(RANDOMNESS1, "round 1");
+        let mut recipe2 = Recipe::with_key(RANDOMNESS1, "round 2");
+        assert_ne!(recipe1.next_randomness(), recipe2.next_randomness());
+    }
+}
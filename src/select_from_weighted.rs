@@ -1,6 +1,11 @@
-use rand::distributions::uniform::SampleUniform;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, HexBinary, Uint128, Uint256};
+use rand::{distributions::uniform::SampleUniform, Rng};
 
-use crate::{int_in_range, integers::Uint};
+use crate::{
+    coinflip, distinct_ints_in_range, int_in_range, integers::Uint, prng::make_prng, shuffle,
+    sub_randomness,
+};
 
 /// Selects one element from a given weighted list.
 ///
@@ -34,159 +39,2384 @@ pub fn select_from_weighted<T: Clone, W: Uint + SampleUniform>(
     randomness: [u8; 32],
     list: &[(T, W)],
 ) -> Result<T, String> {
-    if list.is_empty() {
+    let index = select_index(randomness, list.iter().map(|(_, weight)| *weight))?;
+    Ok(list[index].0.clone())
+}
+
+/// Returns whether [`select_from_weighted`] selects the given candidate for this randomness.
+///
+/// This is useful for dispute resolution, e.g. to show that a specific entrant was not
+/// selected. Candidates are compared by value, so an element that occurs multiple times
+/// in the list is considered selected if any of its occurrences is selected.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, was_selected};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+/// assert!(was_selected(randomness, &list, &"bob").unwrap());
+/// assert!(!was_selected(randomness, &list, &"alice").unwrap());
+/// ```
+pub fn was_selected<T: Eq>(
+    randomness: [u8; 32],
+    list: &[(T, u32)],
+    candidate: &T,
+) -> Result<bool, String> {
+    let index = select_index(randomness, list.iter().map(|(_, weight)| *weight))?;
+    Ok(list[index].0 == *candidate)
+}
+
+/// Selects one element from a given weighted list and returns it together with its weight
+/// and the total weight of the list.
+///
+/// This allows frontends to display the odds of a draw, which are `weight / total_weight`.
+/// The selected element is the same as for [`select_from_weighted`].
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, select_and_report};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+/// let (winner, weight, total_weight) = select_and_report(randomness, &list).unwrap();
+/// assert_eq!(winner, "bob");
+/// assert_eq!(weight, 20);
+/// assert_eq!(total_weight, 60);
+/// println!("{winner} won with odds of {weight}/{total_weight}");
+/// ```
+pub fn select_and_report<T: Clone>(
+    randomness: [u8; 32],
+    list: &[(T, u32)],
+) -> Result<(T, u32, u32), String> {
+    let weights = list.iter().map(|(_, weight)| *weight);
+    let total_weight = total_weight(weights.clone())?;
+    let index = select_index(randomness, weights)?;
+    let (element, weight) = &list[index];
+    Ok((element.clone(), *weight, total_weight))
+}
+
+/// Selects a trait from weighted options and returns it together with its rarity, e.g. to
+/// record how rare each trait of a minted NFT is.
+///
+/// The selection is the same as in [`select_from_weighted`]. The rarity is the probability
+/// of the selected option, i.e. its weight divided by the total weight.
+///
+/// The list must not be empty. Each option must have a non-zero weight.
+///
+/// ## Examples
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{randomness_from_str, select_trait};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let hats = vec![("cap", 60u32), ("crown", 5u32), ("beanie", 35u32)];
+/// let (hat, rarity) = select_trait(randomness, &hats).unwrap();
+/// assert_eq!(hat, "cap");
+/// assert_eq!(rarity, Decimal::percent(60));
+/// ```
+pub fn select_trait<T: Clone>(
+    randomness: [u8; 32],
+    options: &[(T, u32)],
+) -> Result<(T, Decimal), String> {
+    let (selected, weight, total_weight) = select_and_report(randomness, options)?;
+    Ok((selected, Decimal::from_ratio(weight, total_weight)))
+}
+
+/// Selects one element from a given weighted list, ignoring the elements at the `exclude` indices.
+///
+/// The weights of excluded elements do not count towards the total weight, i.e. the result
+/// is the same as calling [`select_from_weighted`] with the excluded elements removed from
+/// the list.
+///
+/// Every remaining element must have a non-zero weight. Returns an error if an excluded
+/// index is out of bounds or if all elements are excluded.
+///
+/// ## Examples
+///
+/// Do not draw the previous winner again:
+///
+/// ```
+/// use nois::{randomness_from_str, select_from_weighted_excluding};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+/// let selected = select_from_weighted_excluding(randomness, &list, &[1]).unwrap();
+/// assert_ne!(selected, "bob");
+/// ```
+pub fn select_from_weighted_excluding<T: Clone>(
+    randomness: [u8; 32],
+    list: &[(T, u32)],
+    exclude: &[usize],
+) -> Result<T, String> {
+    if let Some(index) = exclude.iter().find(|index| **index >= list.len()) {
+        return Err(format!("Excluded index {index} is out of bounds"));
+    }
+    let remaining: Vec<&(T, u32)> = list
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !exclude.contains(index))
+        .map(|(_, element)| element)
+        .collect();
+    if remaining.is_empty() {
+        return Err(String::from("All elements are excluded"));
+    }
+    let index = select_index(randomness, remaining.iter().map(|(_, weight)| *weight))?;
+    Ok(remaining[index].0.clone())
+}
+
+/// Selects one element from a given weighted list, skipping the previous winner, e.g. to
+/// avoid back-to-back wins of the same entrant.
+///
+/// All entries equal to `previous_winner` are excluded like in
+/// [`select_from_weighted_excluding`]. If `previous_winner` is `None` or not in the list, this
+/// is the same as [`select_from_weighted`]. If the previous winner is the only entrant, it is
+/// selected again, such that a single entrant list always has a winner.
+///
+/// The list must not be empty. Each element must have a non-zero weight.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, select_excluding_previous};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+/// let winner = select_excluding_previous(randomness, &list, Some(&"carol")).unwrap();
+/// assert_ne!(winner, "carol");
+///
+/// // A single entrant wins again
+/// let winner = select_excluding_previous(randomness, &[("alice", 10u32)], Some(&"alice")).unwrap();
+/// assert_eq!(winner, "alice");
+/// ```
+pub fn select_excluding_previous<T: Clone + Eq>(
+    randomness: [u8; 32],
+    list: &[(T, u32)],
+    previous_winner: Option<&T>,
+) -> Result<T, String> {
+    let exclude: Vec<usize> = match previous_winner {
+        Some(previous) => list
+            .iter()
+            .enumerate()
+            .filter(|(_, (element, _))| element == previous)
+            .map(|(index, _)| index)
+            .collect(),
+        None => Vec::new(),
+    };
+    if exclude.len() == list.len() {
+        // The previous winner is the only entrant (or the list is empty)
+        return select_from_weighted(randomness, list);
+    }
+    select_from_weighted_excluding(randomness, list, &exclude)
+}
+
+/// Selects a denom weighted by its allocation, e.g. for airdrop tooling.
+///
+/// This uses the same selection as [`select_from_weighted`] with u128 weights. Denoms with
+/// a zero allocation are never selected.
+///
+/// Returns an error if the list is empty or the total allocation is zero.
+///
+/// ## Examples
+///
+/// ```
+/// use cosmwasm_std::Uint128;
+/// use nois::{randomness_from_str, select_coin};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let allocations = vec![
+///     ("unois".to_string(), Uint128::new(5_000_000_000)),
+///     ("uatom".to_string(), Uint128::new(1_200_000_000)),
+///     ("ujuno".to_string(), Uint128::zero()),
+/// ];
+/// let denom = select_coin(randomness, &allocations).unwrap();
+/// assert_ne!(denom, "ujuno");
+/// ```
+pub fn select_coin(
+    randomness: [u8; 32],
+    allocations: &[(String, Uint128)],
+) -> Result<String, String> {
+    if allocations.is_empty() {
+        return Err(String::from("List must not be empty"));
+    }
+    let mut total = Uint128::zero();
+    for (_, amount) in allocations {
+        total = total
+            .checked_add(*amount)
+            .map_err(|_| String::from("Total allocation exceeds the Uint128 range"))?;
+    }
+    if total.is_zero() {
+        return Err(String::from("Total allocation must not be zero"));
+    }
+
+    let eligible: Vec<&(String, Uint128)> = allocations
+        .iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect();
+    let index = select_index(randomness, eligible.iter().map(|(_, amount)| amount.u128()))?;
+    Ok(eligible[index].0.clone())
+}
+
+/// Selects a staker with a probability proportional to their stake-seconds, e.g. for loyalty raffles.
+///
+/// Each staker is given as `(element, amount, stake_time)` where `stake_time` is in the same
+/// unit as `now` (e.g. seconds since the epoch). The weight of a staker is
+/// `amount * (now - stake_time)`, computed as `Uint256` such that it cannot overflow.
+/// Stakers with a weight of zero are never selected.
+///
+/// Returns an error if the list is empty, if a stake time is later than `now` or if the
+/// total weight is zero.
+///
+/// ## Examples
+///
+/// ```
+/// use cosmwasm_std::Uint128;
+/// use nois::{randomness_from_str, select_by_stake_seconds};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let now = 1_700_000_000;
+/// let stakers = vec![
+///     ("alice", Uint128::new(1_000_000), now - 86_400 * 30),
+///     ("bob", Uint128::new(5_000_000), now - 86_400),
+///     ("carol", Uint128::new(9_000_000), now),
+/// ];
+/// let winner = select_by_stake_seconds(randomness, &stakers, now).unwrap();
+/// assert_ne!(winner, "carol"); // staked right now
+/// ```
+pub fn select_by_stake_seconds<T: Clone>(
+    randomness: [u8; 32],
+    stakers: &[(T, Uint128, u64)],
+    now: u64,
+) -> Result<T, String> {
+    if stakers.is_empty() {
         return Err(String::from("List must not be empty"));
     }
+    let mut weights = Vec::with_capacity(stakers.len());
+    let mut total = Uint256::zero();
+    for (_, amount, stake_time) in stakers {
+        let duration = now
+            .checked_sub(*stake_time)
+            .ok_or_else(|| String::from("Stake time must not be later than now"))?;
+        let weight = amount.full_mul(duration);
+        total = total
+            .checked_add(weight)
+            .map_err(|_| String::from("Total weight exceeds the Uint256 range"))?;
+        weights.push(weight);
+    }
+    if total.is_zero() {
+        return Err(String::from("Total weight must not be zero"));
+    }
+
+    let r = uint256_below(randomness, total);
+    let mut weight_sum = Uint256::zero();
+    for (index, weight) in weights.into_iter().enumerate() {
+        weight_sum += weight;
+        if r < weight_sum {
+            return Ok(stakers[index].0.clone());
+        }
+    }
+    // This point should never be reached
+    panic!("No element selected")
+}
+
+/// Returns a uniformly distributed value in \[0, end) using rejection sampling.
+/// `end` must not be zero.
+fn uint256_below(randomness: [u8; 32], end: Uint256) -> Uint256 {
+    debug_assert!(!end.is_zero());
+    // Mask off the bits above the highest bit of `end - 1` to keep the rejection rate below 50%
+    let max = (end - Uint256::one()).to_be_bytes();
+    let mut mask = [0u8; 32];
+    let mut leading = true;
+    for (m, byte) in mask.iter_mut().zip(max) {
+        if leading && byte != 0 {
+            leading = false;
+            *m = u8::MAX >> byte.leading_zeros();
+        } else if !leading {
+            *m = u8::MAX;
+        }
+    }
+
+    let mut rng = make_prng(randomness);
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill(&mut bytes);
+        for (byte, m) in bytes.iter_mut().zip(mask) {
+            *byte &= m;
+        }
+        let candidate = Uint256::from_be_bytes(bytes);
+        if candidate < end {
+            return candidate;
+        }
+    }
+}
+
+/// Selects one element from a given weighted list and returns it together with the list
+/// in which the winner's weight is transformed by `decay`.
+///
+/// This allows keeping the winner eligible for a follow-up draw (e.g. a consolation prize)
+/// with a reduced weight. The selected element is the same as for [`select_from_weighted`].
+/// Note that a decayed weight of 0 makes the returned list invalid for [`select_from_weighted`].
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, select_and_reweight, select_from_weighted};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+/// let (winner, list) = select_and_reweight(randomness, list, |weight| weight / 2).unwrap();
+/// assert_eq!(winner, "bob");
+/// assert_eq!(list, vec![("alice", 10), ("bob", 10), ("carol", 30)]);
+/// ```
+pub fn select_and_reweight<T: Clone>(
+    randomness: [u8; 32],
+    mut list: Vec<(T, u32)>,
+    decay: impl Fn(u32) -> u32,
+) -> Result<(T, Vec<(T, u32)>), String> {
+    let index = select_index(randomness, list.iter().map(|(_, weight)| *weight))?;
+    let winner = list[index].0.clone();
+    list[index].1 = decay(list[index].1);
+    Ok((winner, list))
+}
+
+/// Performs `total_draws` weighted draws from the list, guaranteeing that every element
+/// is drawn at least `floor` times.
+///
+/// First, every element is assigned `floor` draws. The remaining draws are weighted
+/// selections as in [`select_from_weighted`], each using an independent sub-randomness.
+/// The result contains the guaranteed draws in list order followed by the weighted draws.
+///
+/// The list must not be empty. Each element must have a non-zero weight.
+/// Returns an error if `floor * list.len()` exceeds `total_draws`.
+///
+/// ## Examples
+///
+/// Distribute 10 reward units such that every validator gets at least 2:
+///
+/// ```
+/// use nois::{randomness_from_str, weighted_draws_with_floor};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let validators = vec![("val1", 50u32), ("val2", 30u32), ("val3", 20u32)];
+/// let draws = weighted_draws_with_floor(randomness, &validators, 10, 2).unwrap();
+/// assert_eq!(draws.len(), 10);
+/// assert_eq!(&draws[..6], &["val1", "val1", "val2", "val2", "val3", "val3"]);
+/// ```
+pub fn weighted_draws_with_floor<T: Clone>(
+    randomness: [u8; 32],
+    list: &[(T, u32)],
+    total_draws: usize,
+    floor: usize,
+) -> Result<Vec<T>, String> {
+    let weights = list.iter().map(|(_, weight)| *weight);
+    total_weight(weights.clone())?;
+
+    let guaranteed = floor
+        .checked_mul(list.len())
+        .filter(|guaranteed| *guaranteed <= total_draws)
+        .ok_or_else(|| {
+            String::from("Floor draws for all elements exceed the total number of draws")
+        })?;
+
+    let mut out = Vec::with_capacity(total_draws);
+    for (element, _) in list {
+        for _ in 0..floor {
+            out.push(element.clone());
+        }
+    }
+    for subrand in sub_randomness(randomness).take(total_draws - guaranteed) {
+        let index = select_index(subrand, weights.clone())?;
+        out.push(list[index].0.clone());
+    }
+    Ok(out)
+}
+
+/// Generates `count` random weights that are all >= 1 and sum up to exactly `total`.
+///
+/// This is useful for procedurally generating balanced loot tables. The weights are a
+/// random partition of `total` obtained by cutting \[0, total] at `count - 1` distinct
+/// random positions, such that every composition of `total` into `count` positive parts
+/// is equally likely. The result is a valid input for [`select_from_weighted`].
+///
+/// Returns an error if `count` is 0 or greater than `total`.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, random_weights_summing_to};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let weights = random_weights_summing_to(randomness, 4, 100).unwrap();
+/// assert_eq!(weights.len(), 4);
+/// assert_eq!(weights.iter().sum::<u32>(), 100);
+/// ```
+pub fn random_weights_summing_to(
+    randomness: [u8; 32],
+    count: usize,
+    total: u32,
+) -> Result<Vec<u32>, String> {
+    if count == 0 {
+        return Err(String::from("Count must not be 0"));
+    }
+    if count as u64 > total as u64 {
+        return Err(String::from("Count must not exceed the total"));
+    }
+
+    let mut cuts = if count > 1 {
+        distinct_ints_in_range(randomness, count - 1, 1, total - 1)
+    } else {
+        Vec::new()
+    };
+    cuts.sort_unstable();
+
+    let mut weights = Vec::with_capacity(count);
+    let mut previous = 0;
+    for cut in cuts.into_iter().chain(std::iter::once(total)) {
+        weights.push(cut - previous);
+        previous = cut;
+    }
+    Ok(weights)
+}
+
+/// Selects one element from a list with selection probabilities given as integer percentages.
+///
+/// The percentages must sum up to exactly 100. This catches misconfigured lists early.
+/// Elements with 0% are allowed and never selected.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, select_from_percentages};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let list = vec![("common", 70u8), ("rare", 25u8), ("legendary", 5u8)];
+/// let selected = select_from_percentages(randomness, &list).unwrap();
+/// assert_eq!(selected, "common");
+///
+/// let misconfigured = vec![("common", 70u8), ("rare", 25u8)];
+/// let err = select_from_percentages(randomness, &misconfigured).unwrap_err();
+/// assert_eq!(err, "Percentages must sum up to 100 but sum up to 95");
+/// ```
+pub fn select_from_percentages<T: Clone>(
+    randomness: [u8; 32],
+    list: &[(T, u8)],
+) -> Result<T, String> {
+    let sum: u32 = list.iter().map(|(_, percentage)| *percentage as u32).sum();
+    if sum != 100 {
+        return Err(format!(
+            "Percentages must sum up to 100 but sum up to {sum}"
+        ));
+    }
+
+    let candidates: Vec<&(T, u8)> = list
+        .iter()
+        .filter(|(_, percentage)| *percentage > 0)
+        .collect();
+    let index = select_index(
+        randomness,
+        candidates.iter().map(|(_, percentage)| *percentage),
+    )?;
+    Ok(candidates[index].0.clone())
+}
+
+/// Selects a discount percentage from weighted tiers, e.g. for promotions.
+///
+/// Each tier is a `(discount_percent, weight)` pair. The tier is selected like in
+/// [`select_from_weighted`] and its discount is returned.
+///
+/// Returns an error if a discount exceeds 100% or the weights are invalid, i.e. the list
+/// is empty or a weight is 0.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, random_discount};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // 70% chance of 5% off, 25% chance of 10% off, 5% chance of 20% off
+/// let discount = random_discount(randomness, &[(5, 70), (10, 25), (20, 5)]).unwrap();
+/// assert!([5, 10, 20].contains(&discount));
+/// ```
+pub fn random_discount(randomness: [u8; 32], tiers: &[(u8, u32)]) -> Result<u8, String> {
+    if let Some((discount, _)) = tiers.iter().find(|(discount, _)| *discount > 100) {
+        return Err(format!("Discount of {discount}% exceeds 100%"));
+    }
+    select_from_weighted(randomness, tiers)
+}
+
+/// Selects a random subset of items whose total weight lies in the band \[min, max],
+/// e.g. for budget-constrained loot drops.
+///
+/// The items are shuffled and then accumulated greedily: an item is added if it does not
+/// push the total above `max`, and the pass stops once the total reaches `min`. The
+/// selected items are returned in the order they were drawn.
+///
+/// Returns an error if `min > max`, an item has a weight of 0 or the greedy pass does not
+/// reach the band. The latter is always the case if no subset within the band exists, but
+/// can also happen for narrow bands. If the band holds at least as many values as the
+/// largest weight, i.e. `max - min + 1 >= largest weight`, the pass always succeeds as long
+/// as the total weight of all items reaches `min`.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, random_subset_in_weight_band};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Item values, the loot drop should be worth 50-80
+/// let items = vec![("sword", 30), ("shield", 25), ("potion", 5), ("ring", 20), ("gem", 15)];
+/// let loot = random_subset_in_weight_band(randomness, &items, 50, 80).unwrap();
+/// let total: u32 = loot
+///     .iter()
+///     .map(|item| items.iter().find(|(name, _)| name == item).unwrap().1)
+///     .sum();
+/// assert!(total >= 50 && total <= 80);
+/// ```
+pub fn random_subset_in_weight_band<T: Clone>(
+    randomness: [u8; 32],
+    items: &[(T, u32)],
+    min: u32,
+    max: u32,
+) -> Result<Vec<T>, String> {
+    if min > max {
+        return Err(String::from(
+            "Minimum weight must not exceed maximum weight",
+        ));
+    }
+    if items.iter().any(|(_, weight)| *weight == 0) {
+        return Err(String::from("All element weights should be >= 1"));
+    }
+
+    let order = shuffle(randomness, (0..items.len()).collect());
+    let mut subset = Vec::new();
+    let mut total = 0u32;
+    for index in order {
+        if total >= min {
+            break;
+        }
+        let (item, weight) = &items[index];
+        // Cannot overflow since total <= max
+        if total as u64 + *weight as u64 <= max as u64 {
+            total += weight;
+            subset.push(item.clone());
+        }
+    }
+
+    if total < min {
+        return Err(format!(
+            "No subset with a total weight in [{min}, {max}] found"
+        ));
+    }
+    Ok(subset)
+}
+
+/// Chooses one of several branches by weight and returns its index.
+///
+/// This is useful for randomized control flow where the outcome is dispatched with a `match`
+/// rather than stored as a value. The weights are interpreted in the same way as in
+/// [`select_from_weighted`], i.e. the same randomness and weights lead to the same index.
+///
+/// The list must not be empty. Each branch must have a non-zero weight.
+///
+/// [`weighted_int`](crate::weighted_int) is an alias of this function.
+///
+/// ## Examples
+///
+/// Pick a strategy for a game bot:
+///
+/// ```
+/// use nois::{choose_branch, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // attack: 50%, defend: 30%, retreat: 20%
+/// let action = match choose_branch(randomness, &[50, 30, 20]).unwrap() {
+///     0 => "attack",
+///     1 => "defend",
+///     2 => "retreat",
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(action, "attack");
+/// ```
+pub fn choose_branch(randomness: [u8; 32], weights: &[u32]) -> Result<usize, String> {
+    select_index(randomness, weights.iter().copied())
+}
+
+/// Selects `k` indices from a list of weights using systematic sampling, e.g. for a fair
+/// selection of validators per epoch.
+///
+/// The cumulative weights are laid out on an axis of length `total_weight` and divided into
+/// `k` intervals of equal length. One random start offset is drawn in the first interval and
+/// the element at the same offset in every interval is selected. This way each index is
+/// selected either `⌊weight * k / total_weight⌋` or `⌈weight * k / total_weight⌉` times,
+/// which is the expected count rounded down or up. Indices whose weight exceeds
+/// `total_weight / k` can occur multiple times.
+///
+/// The indices are returned in ascending order. The list must not be empty and each weight
+/// must be non-zero.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, systematic_sample};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Stakes of 4 validators
+/// let stakes = [500, 300, 150, 50];
+/// let selected = systematic_sample(randomness, &stakes, 10).unwrap();
+/// assert_eq!(selected.len(), 10);
+/// // Validator 0 has half of the stake and gets exactly half of the slots
+/// assert_eq!(selected.iter().filter(|index| **index == 0).count(), 5);
+/// ```
+pub fn systematic_sample(
+    randomness: [u8; 32],
+    weights: &[u32],
+    k: usize,
+) -> Result<Vec<usize>, String> {
+    let total_weight = total_weight(weights.iter().copied())?;
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+
+    // All positions are scaled by k such that the interval length is the integer total_weight
+    let total_weight = total_weight as u128;
+    let start = int_in_range(randomness, 0, total_weight - 1);
+    let mut selected = Vec::with_capacity(k);
+    let mut index = 0;
+    let mut upper_bound = weights[0] as u128 * k as u128;
+    for i in 0..k as u128 {
+        let position = start + i * total_weight;
+        while position >= upper_bound {
+            index += 1;
+            upper_bound += weights[index] as u128 * k as u128;
+        }
+        selected.push(index);
+    }
+    Ok(selected)
+}
+
+/// Selects an index from a list of weights like [`choose_branch`], using a secondary
+/// randomness to resolve draws that land exactly on a cumulative boundary.
+///
+/// The `primary` randomness draws a ticket in \[1, total weight]. If the ticket is the last
+/// ticket of an element, i.e. it lies on the boundary to the next element, a coinflip on the
+/// `secondary` randomness decides between the two adjacent candidates. The boundary after
+/// the last element is shared with the first element. This way every element gives away half
+/// of its upper boundary ticket and receives half of its lower one, such that the selection
+/// probabilities remain proportional to the weights.
+///
+/// The list must not be empty. Each weight must be non-zero.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, select_index_from_weighted_with_tiebreak, sub_randomness};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+/// let mut provider = sub_randomness(randomness);
+///
+/// let index = select_index_from_weighted_with_tiebreak(
+///     provider.provide(),
+///     provider.provide(),
+///     &[50, 30, 20],
+/// ).unwrap();
+/// assert!(index < 3);
+/// ```
+pub fn select_index_from_weighted_with_tiebreak(
+    primary: [u8; 32],
+    secondary: [u8; 32],
+    weights: &[u32],
+) -> Result<usize, String> {
+    let total_weight = total_weight(weights.iter().copied())?;
+
+    let r = int_in_range(primary, 1, total_weight);
+    let mut weight_sum = 0u32;
+    for (index, weight) in weights.iter().enumerate() {
+        weight_sum += weight;
+        if r < weight_sum {
+            return Ok(index);
+        }
+        if r == weight_sum {
+            let next = (index + 1) % weights.len();
+            return Ok(if coinflip(secondary).is_heads() {
+                next
+            } else {
+                index
+            });
+        }
+    }
+    // This point should never be reached
+    panic!("No element selected")
+}
+
+/// A compact proof for the result of [`draw_winner_with_proof`].
+#[cw_serde]
+pub struct WinnerProof {
+    /// The randomness used for the draw
+    pub randomness: HexBinary,
+    /// The sum of all entrant weights (i.e. the number of tickets)
+    pub total_weight: u32,
+    /// The ticket in the range [1, total_weight] that was drawn
+    pub drawn_ticket: u32,
+}
+
+/// Draws a winner from a weighted list of entrants and returns it together with a proof.
+///
+/// Each entrant holds `weight` consecutive tickets in list order. The winner is the owner
+/// of the drawn ticket. The winner is the same as for [`select_from_weighted`].
+/// Use [`verify_winner`] to recheck the result, e.g. in a frontend.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{draw_winner_with_proof, randomness_from_str, verify_winner};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let entrants = vec![("alice", 3u32), ("bob", 10u32), ("carol", 7u32)];
+/// let (winner, proof) = draw_winner_with_proof(randomness, &entrants).unwrap();
+/// assert_eq!(proof.total_weight, 20);
+/// assert!(verify_winner(&entrants, &proof, &winner));
+/// ```
+pub fn draw_winner_with_proof<T: Clone>(
+    randomness: [u8; 32],
+    entrants: &[(T, u32)],
+) -> Result<(T, WinnerProof), String> {
+    let index = select_index(randomness, entrants.iter().map(|(_, weight)| *weight))?;
+    // Cannot overflow since select_index checked the total
+    let total_weight = entrants.iter().map(|(_, weight)| *weight).sum();
+    let proof = WinnerProof {
+        randomness: randomness.into(),
+        total_weight,
+        drawn_ticket: int_in_range(randomness, 1, total_weight),
+    };
+    Ok((entrants[index].0.clone(), proof))
+}
+
+/// Verifies the result of [`draw_winner_with_proof`].
+///
+/// This recomputes the drawn ticket from the randomness in the proof and walks
+/// the cumulative weights of the entrants to find its owner.
+pub fn verify_winner<T: PartialEq>(entrants: &[(T, u32)], proof: &WinnerProof, winner: &T) -> bool {
+    let mut total_weight = 0u32;
+    for (_, weight) in entrants {
+        if *weight == 0 {
+            return false;
+        }
+        total_weight = match total_weight.checked_add(*weight) {
+            Some(sum) => sum,
+            None => return false,
+        };
+    }
+    if total_weight == 0 || total_weight != proof.total_weight {
+        return false;
+    }
+    let Ok(randomness) = proof.randomness.to_array() else {
+        return false;
+    };
+    if int_in_range(randomness, 1, total_weight) != proof.drawn_ticket {
+        return false;
+    }
+
+    let mut weight_sum = 0u32;
+    for (entrant, weight) in entrants {
+        weight_sum += weight;
+        if proof.drawn_ticket <= weight_sum {
+            return entrant == winner;
+        }
+    }
+    false
+}
+
+/// Spins a wheel of fortune with segments of different sizes.
+///
+/// Returns the selected segment and the landing angle in degrees. The wheel is divided into
+/// arcs proportional to the segment weights, starting at 0° in list order. The angle
+/// is in [0, 360) and lies within the arc of the selected segment, which is useful
+/// for animations. The selected segment is the same as for [`select_from_weighted`].
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_from_str, spin_wheel};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // "jackpot" covers 0°-36°, "free spin" 36°-144°, "nothing" 144°-360°
+/// let segments = vec![("jackpot", 1u32), ("free spin", 3u32), ("nothing", 6u32)];
+/// let (segment, angle) = spin_wheel(randomness, &segments).unwrap();
+/// assert_eq!(segment, "free spin");
+/// assert!(angle >= 36.0 && angle < 144.0);
+/// ```
+pub fn spin_wheel<T: Clone>(
+    randomness: [u8; 32],
+    segments: &[(T, u32)],
+) -> Result<(T, f64), String> {
+    let index = select_index(randomness, segments.iter().map(|(_, weight)| *weight))?;
+    // Cannot overflow since select_index checked the total
+    let total_weight: u32 = segments.iter().map(|(_, weight)| *weight).sum();
+    // The same ticket in [1, total_weight] that select_index used
+    let ticket = int_in_range(randomness, 1, total_weight);
+
+    // Use an independent randomness for the position within the ticket
+    let mut rng = make_prng(sub_randomness(randomness).provide());
+    let fraction: f64 = rng.gen(); // in [0, 1)
+    let angle = ((ticket - 1) as f64 + fraction) / total_weight as f64 * 360.0;
+
+    // Guard against rounding errors at the end of the arc
+    let end: u32 = segments[..=index].iter().map(|(_, weight)| *weight).sum();
+    let end_angle = end as f64 / total_weight as f64 * 360.0;
+    let angle = if angle >= end_angle {
+        end_angle - (end_angle - ((ticket - 1) as f64 / total_weight as f64 * 360.0)) / 2.0
+    } else {
+        angle
+    };
+    Ok((segments[index].0.clone(), angle))
+}
+
+/// Weighted selection with a pity mechanic as known from gacha games.
+///
+/// Every draw that does not hit the rare element increases the rare element's effective weight
+/// by `boost`. After `guarantee_after` consecutive misses, the next draw is guaranteed to hit.
+/// The counter is reset when the rare element is drawn.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{sub_randomness, randomness_from_str, PityState};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // common: 90, uncommon: 9, legendary: 1 (index 2)
+/// let mut state = PityState::new(vec![90, 9, 1], 2, 5, 50).unwrap();
+/// for subrand in sub_randomness(randomness).take(10) {
+///     let index = state.draw(subrand);
+///     assert!(index < 3);
+/// }
+/// ```
+#[cw_serde]
+pub struct PityState {
+    weights: Vec<u32>,
+    rare_index: usize,
+    boost: u32,
+    guarantee_after: u32,
+    counter: u32,
+}
+
+impl PityState {
+    /// Creates a new state with a counter of 0.
+    ///
+    /// The weights must not be empty and each weight must be non-zero.
+    /// `rare_index` must be a valid index into the weights.
+    pub fn new(
+        weights: Vec<u32>,
+        rare_index: usize,
+        boost: u32,
+        guarantee_after: u32,
+    ) -> Result<Self, String> {
+        if weights.is_empty() {
+            return Err(String::from("List must not be empty"));
+        }
+        if weights.contains(&0) {
+            return Err(String::from("All element weights should be >= 1"));
+        }
+        if rare_index >= weights.len() {
+            return Err(String::from("Rare index out of bounds"));
+        }
+        Ok(Self {
+            weights,
+            rare_index,
+            boost,
+            guarantee_after,
+            counter: 0,
+        })
+    }
+
+    /// The number of consecutive draws that did not hit the rare element.
+    pub fn counter(&self) -> u32 {
+        self.counter
+    }
+
+    /// Draws an index and updates the pity counter.
+    pub fn draw(&mut self, randomness: [u8; 32]) -> usize {
+        let index = if self.counter >= self.guarantee_after {
+            self.rare_index
+        } else {
+            let bonus = self.boost as u128 * self.counter as u128;
+            let weights = self.weights.iter().enumerate().map(|(index, weight)| {
+                if index == self.rare_index {
+                    *weight as u128 + bonus
+                } else {
+                    *weight as u128
+                }
+            });
+            select_index(randomness, weights).expect("weights are validated on construction")
+        };
+
+        if index == self.rare_index {
+            self.counter = 0;
+        } else {
+            self.counter = self.counter.saturating_add(1);
+        }
+        index
+    }
+}
+
+/// Random selection that guarantees every option appears exactly once per cycle.
+///
+/// The options are drawn from a bag without replacement. Once the bag is empty, it is
+/// refilled with all options. This way every option appears once within each cycle of
+/// `options.len()` draws, while the order within a cycle is random.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{sub_randomness, randomness_from_str, RoundRobinRandom};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut bag = RoundRobinRandom::new(vec!["map1", "map2", "map3"]).unwrap();
+/// let mut provider = sub_randomness(randomness);
+/// let mut cycle: Vec<&str> = (0..3).map(|_| bag.next(provider.provide())).collect();
+/// cycle.sort();
+/// assert_eq!(cycle, vec!["map1", "map2", "map3"]);
+/// ```
+#[cw_serde]
+pub struct RoundRobinRandom<T> {
+    options: Vec<T>,
+    remaining: Vec<T>,
+}
+
+impl<T: Clone> RoundRobinRandom<T> {
+    /// Creates a new instance with a full bag. The options must not be empty.
+    pub fn new(options: Vec<T>) -> Result<Self, String> {
+        if options.is_empty() {
+            return Err(String::from("List must not be empty"));
+        }
+        Ok(Self {
+            remaining: options.clone(),
+            options,
+        })
+    }
+
+    /// The number of draws left until the bag is refilled.
+    pub fn remaining(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Draws the next option from the bag, refilling it first if it is empty.
+    #[allow(clippy::should_implement_trait)] // Iterator::next does not take an argument
+    pub fn next(&mut self, randomness: [u8; 32]) -> T {
+        if self.remaining.is_empty() {
+            self.remaining = self.options.clone();
+        }
+        let index = int_in_range(randomness, 0, self.remaining.len() - 1);
+        self.remaining.swap_remove(index)
+    }
+}
+
+/// Weighted selection where every element can be drawn at most `cap` times.
+///
+/// Elements that reached their cap are excluded from subsequent draws. Once all elements
+/// are capped out, [`CappedWeightedPool::draw`] returns `None`.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{sub_randomness, randomness_from_str, CappedWeightedPool};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Three resources, each can be assigned twice
+/// let mut pool = CappedWeightedPool::new(vec![50, 30, 20], 2).unwrap();
+/// let mut draws = 0;
+/// for subrand in sub_randomness(randomness) {
+///     match pool.draw(subrand) {
+///         Some(index) => assert!(index < 3),
+///         None => break,
+///     }
+///     draws += 1;
+/// }
+/// assert_eq!(draws, 6);
+/// ```
+#[cw_serde]
+pub struct CappedWeightedPool {
+    weights: Vec<u32>,
+    remaining: Vec<u32>,
+}
+
+impl CappedWeightedPool {
+    /// Creates a pool in which every element can be drawn `cap` times.
+    ///
+    /// The weights must not be empty and each weight must be non-zero.
+    pub fn new(weights: Vec<u32>, cap: u32) -> Result<Self, String> {
+        if weights.is_empty() {
+            return Err(String::from("List must not be empty"));
+        }
+        if weights.contains(&0) {
+            return Err(String::from("All element weights should be >= 1"));
+        }
+        Ok(Self {
+            remaining: vec![cap; weights.len()],
+            weights,
+        })
+    }
+
+    /// The number of times the element at `index` can still be drawn.
+    pub fn remaining(&self, index: usize) -> u32 {
+        self.remaining[index]
+    }
+
+    /// Draws an index among the elements that did not reach their cap yet.
+    /// Returns `None` if all elements are capped out.
+    pub fn draw(&mut self, randomness: [u8; 32]) -> Option<usize> {
+        let available: Vec<usize> = (0..self.weights.len())
+            .filter(|index| self.remaining[*index] > 0)
+            .collect();
+        if available.is_empty() {
+            return None;
+        }
+        let weights = available.iter().map(|index| self.weights[*index] as u128);
+        let selected =
+            select_index(randomness, weights).expect("weights are validated on construction");
+        let index = available[selected];
+        self.remaining[index] -= 1;
+        Some(index)
+    }
+}
+
+/// The maximum nesting depth of a [`LootTable`]
+pub const MAX_LOOT_TABLE_DEPTH: usize = 16;
+
+/// An entry of a [`LootTable`]
+#[cw_serde]
+pub enum LootEntry<T> {
+    /// A concrete item
+    Item(T),
+    /// A nested table that is rolled when this entry is selected
+    Table(Box<LootTable<T>>),
+}
+
+/// A weighted loot table whose entries are either items or nested tables, e.g. a main
+/// table pointing to a "rare gems" sub-table.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, LootEntry, LootTable};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let gems = LootTable::new(vec![
+///     (LootEntry::Item("ruby"), 1),
+///     (LootEntry::Item("emerald"), 2),
+/// ]);
+/// let main = LootTable::new(vec![
+///     (LootEntry::Item("gold"), 80),
+///     (LootEntry::Item("potion"), 15),
+///     (LootEntry::Table(Box::new(gems)), 5),
+/// ]);
+/// let loot = main.roll(randomness).unwrap();
+/// assert!(["gold", "potion", "ruby", "emerald"].contains(&loot));
+/// ```
+#[cw_serde]
+pub struct LootTable<T> {
+    entries: Vec<(LootEntry<T>, u32)>,
+}
+
+impl<T: Clone> LootTable<T> {
+    /// Creates a table from `(entry, weight)` pairs.
+    pub fn new(entries: Vec<(LootEntry<T>, u32)>) -> Self {
+        Self { entries }
+    }
+
+    /// Rolls on this table. If a nested table is selected, it is rolled on as well until
+    /// an item is found. Each level uses its own sub-randomness.
+    ///
+    /// Returns an error if a table on the way is empty or has a weight of 0, or if the
+    /// nesting exceeds [`MAX_LOOT_TABLE_DEPTH`].
+    pub fn roll(&self, randomness: [u8; 32]) -> Result<T, String> {
+        let mut provider = sub_randomness(randomness);
+        let mut table = self;
+        for _ in 0..MAX_LOOT_TABLE_DEPTH {
+            let weights = table.entries.iter().map(|(_, weight)| *weight);
+            let index = select_index(provider.provide(), weights)?;
+            match &table.entries[index].0 {
+                LootEntry::Item(item) => return Ok(item.clone()),
+                LootEntry::Table(nested) => table = nested,
+            }
+        }
+        Err(format!(
+            "Loot table nesting exceeds the maximum depth of {MAX_LOOT_TABLE_DEPTH}"
+        ))
+    }
+}
+
+/// Validates a list of weights and returns the total weight.
+fn total_weight<W: Uint>(weights: impl Iterator<Item = W>) -> Result<W, String> {
+    let mut total_weight = W::ZERO;
+    let mut len = 0usize;
+    for weight in weights {
+        if weight == W::ZERO {
+            return Err(String::from("All element weights should be >= 1"));
+        }
+        total_weight = total_weight
+            .checked_add(weight)
+            .ok_or_else(|| String::from("Total weight is greater than maximum value of u32"))?;
+        len += 1;
+    }
+
+    if len == 0 {
+        return Err(String::from("List must not be empty"));
+    }
+
+    debug_assert!(
+        total_weight > W::ZERO,
+        "we know we have a non-empty list of non-zero elements"
+    );
+    Ok(total_weight)
+}
+
+/// Selects an index from a list of weights. This is the shared implementation
+/// of [`select_from_weighted`] and [`choose_branch`].
+fn select_index<W: Uint + SampleUniform>(
+    randomness: [u8; 32],
+    weights: impl Iterator<Item = W> + Clone,
+) -> Result<usize, String> {
+    let total_weight = total_weight(weights.clone())?;
+
+    let r = int_in_range::<W>(randomness, W::ONE, total_weight);
+    let mut weight_sum = W::ZERO;
+    for (index, weight) in weights.enumerate() {
+        weight_sum += weight;
+        if r <= weight_sum {
+            return Ok(index);
+        }
+    }
+    // This point should never be reached
+    panic!("No element selected")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn select_from_weighted_works() {
+        let elements: Vec<(char, u32)> = vec![('a', 1), ('b', 5), ('c', 4)];
+        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
+        assert_eq!(picked, 'c');
+
+        // Element type is Clone but not Copy
+        #[derive(PartialEq, Debug, Clone)]
+        struct Color(String);
+        let elements = vec![
+            (Color("red".into()), 12u32),
+            (Color("blue".to_string()), 15u32),
+            (Color("green".to_string()), 8u32),
+            (Color("orange".to_string()), 21u32),
+            (Color("pink".to_string()), 11u32),
+        ];
+        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
+        assert_eq!(picked, Color("orange".to_string()));
+
+        // Test for u128
+        let elements = vec![
+            (Color("red".into()), 12u128),
+            (Color("blue".to_string()), 15u128),
+            (Color("green".to_string()), 8u128),
+            (Color("orange".to_string()), 21u128),
+            (Color("pink".to_string()), 11u128),
+        ];
+        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
+        assert_eq!(picked, Color("blue".to_string()));
+
+        // Pick from slice
+        let selection = &elements[0..3];
+        let picked = select_from_weighted(RANDOMNESS1, selection).unwrap();
+        assert_eq!(picked, Color("red".to_string()));
+    }
+
+    #[test]
+    fn select_from_weighted_fails_on_empty_list() {
+        //This will check that the list is empty
+        let elements: Vec<(i32, u32)> = vec![];
+
+        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+
+        // Check that the selected element has the expected weight
+        assert_eq!(err, "List must not be empty");
+    }
+
+    #[test]
+    fn select_from_weighted_fails_on_element_weight_less_than_1() {
+        let elements: Vec<(i32, u32)> = vec![(1, 5), (2, 4), (-3, 0)];
+
+        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+
+        // Check that the selected element has the expected weight
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn select_from_weighted_fails_with_total_weight_too_high() {
+        let elements: Vec<(i32, u128)> = vec![(1, u128::MAX), (2, 1)];
+
+        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+
+        // Check that the selected element has the expected weight
+        assert_eq!(err, "Total weight is greater than maximum value of u32");
+    }
+
+    #[test]
+    fn select_from_weighted_distribution_is_uniform() {
+        /// This test will generate a huge amount  of subrandomness
+        /// then checks that the distribution is expected within a range of 1%
+        use crate::sub_randomness::sub_randomness;
+        use std::collections::HashMap;
+
+        const TEST_SAMPLE_SIZE: usize = 1_000_000;
+        const ACCURACY: f32 = 0.01;
+        // This test needs the sum of the weights to be equal to 1.
+        // Although the function should work as expected for weights that do not equal 1
+        let elements: Vec<(String, u32)> = vec![
+            (String::from("a"), 100),
+            (String::from("b"), 200),
+            (String::from("c"), 30),
+            (String::from("d"), 70),
+            (String::from("e"), 600),
+        ];
+        let total_weight = elements.iter().map(|element| element.1).sum::<u32>();
+        println!("total weight: {}", total_weight);
+
+        let mut result = vec![];
+
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            result.push(select_from_weighted(subrand, &elements).unwrap());
+        }
+
+        let mut histogram = HashMap::new();
+
+        for element in result {
+            let count = histogram.entry(element).or_insert(0);
+            *count += 1;
+        }
+
+        // This will assert on all the elements of the data 1 by 1 and check if their occurence is within the 1% expected range
+        for (bin, count) in histogram {
+            let probability = elements.iter().find(|e| e.0 == bin).map(|e| e.1).unwrap() as f32
+                / total_weight as f32;
+            let estimated_count_for_uniform_distribution = TEST_SAMPLE_SIZE as f32 * probability;
+            let estimation_min: i32 =
+                (estimated_count_for_uniform_distribution * (1_f32 - ACCURACY)) as i32;
+            let estimation_max: i32 =
+                (estimated_count_for_uniform_distribution * (1_f32 + ACCURACY)) as i32;
+            println!(
+                "estimation {}, max: {}, min: {}",
+                estimated_count_for_uniform_distribution, estimation_max, estimation_min
+            );
+            println!("{}: {}", bin, count);
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn select_and_reweight_works() {
+        let list = vec![('a', 12u32), ('b', 15), ('c', 8), ('d', 21), ('e', 11)];
+        let expected_winner = select_from_weighted(RANDOMNESS1, &list).unwrap();
+        let (winner, reweighted) =
+            select_and_reweight(RANDOMNESS1, list.clone(), |w| w / 3).unwrap();
+        assert_eq!(winner, expected_winner);
+
+        // The winner's weight changes as specified and others are unchanged
+        assert_eq!(reweighted.len(), list.len());
+        for (original, new) in list.iter().zip(reweighted.iter()) {
+            assert_eq!(original.0, new.0);
+            if original.0 == winner {
+                assert_eq!(new.1, original.1 / 3);
+            } else {
+                assert_eq!(new.1, original.1);
+            }
+        }
+
+        let err = select_and_reweight::<char>(RANDOMNESS1, vec![], |w| w).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = select_and_reweight(RANDOMNESS1, vec![('a', 0u32)], |w| w).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn select_from_percentages_works() {
+        let elements = vec![('a', 10u8), ('b', 50), ('c', 40)];
+        let picked = select_from_percentages(RANDOMNESS1, &elements).unwrap();
+        let weighted = vec![('a', 10u8), ('b', 50), ('c', 40)];
+        assert_eq!(
+            picked,
+            select_from_weighted(RANDOMNESS1, &weighted).unwrap()
+        );
+
+        let elements = vec![('a', 100u8)];
+        let picked = select_from_percentages(RANDOMNESS1, &elements).unwrap();
+        assert_eq!(picked, 'a');
+
+        // Zero percentage elements are never selected
+        use crate::sub_randomness::sub_randomness;
+        let elements = vec![('a', 0u8), ('b', 60), ('c', 0), ('d', 40)];
+        for subrand in sub_randomness(RANDOMNESS1).take(200) {
+            let picked = select_from_percentages(subrand, &elements).unwrap();
+            assert!(picked == 'b' || picked == 'd');
+        }
+    }
+
+    #[test]
+    fn select_from_percentages_fails_for_sum_not_100() {
+        let err = select_from_percentages(RANDOMNESS1, &[('a', 30u8), ('b', 30)]).unwrap_err();
+        assert_eq!(err, "Percentages must sum up to 100 but sum up to 60");
+
+        let err = select_from_percentages(RANDOMNESS1, &[('a', 255u8), ('b', 255)]).unwrap_err();
+        assert_eq!(err, "Percentages must sum up to 100 but sum up to 510");
+
+        let err = select_from_percentages::<char>(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "Percentages must sum up to 100 but sum up to 0");
+    }
+
+    #[test]
+    fn was_selected_works() {
+        let list = vec![("a", 12u32), ("b", 15u32), ("c", 8u32), ("d", 21u32)];
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(20) {
+            let selected = select_from_weighted(randomness, &list).unwrap();
+            for (element, _) in &list {
+                assert_eq!(
+                    was_selected(randomness, &list, element).unwrap(),
+                    *element == selected
+                );
+            }
+            // Candidates not in the list are never selected
+            assert!(!was_selected(randomness, &list, &"z").unwrap());
+        }
+
+        let err = was_selected(RANDOMNESS1, &Vec::<(&str, u32)>::new(), &"a").unwrap_err();
+        assert_eq!(err, "List must not be empty");
+    }
+
+    #[test]
+    fn select_from_weighted_excluding_works() {
+        let list = vec![("a", 12u32), ("b", 15u32), ("c", 8u32), ("d", 21u32)];
+
+        // Nothing excluded is the same as select_from_weighted
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(20) {
+            assert_eq!(
+                select_from_weighted_excluding(randomness, &list, &[]).unwrap(),
+                select_from_weighted(randomness, &list).unwrap()
+            );
+        }
+
+        // Same as selecting from the reduced list
+        let reduced = vec![("a", 12u32), ("c", 8u32)];
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(20) {
+            let selected = select_from_weighted_excluding(randomness, &list, &[1, 3]).unwrap();
+            assert_eq!(
+                selected,
+                select_from_weighted(randomness, &reduced).unwrap()
+            );
+        }
+
+        // Only one remaining
+        let selected = select_from_weighted_excluding(RANDOMNESS1, &list, &[0, 1, 3]).unwrap();
+        assert_eq!(selected, "c");
+
+        // Duplicate exclusions are fine
+        let selected = select_from_weighted_excluding(RANDOMNESS1, &list, &[3, 3, 0, 1]).unwrap();
+        assert_eq!(selected, "c");
+
+        // Excluded elements may have weight 0
+        let list = vec![("a", 0u32), ("b", 5u32)];
+        let selected = select_from_weighted_excluding(RANDOMNESS1, &list, &[0]).unwrap();
+        assert_eq!(selected, "b");
+    }
+
+    #[test]
+    fn select_from_weighted_excluding_fails_for_invalid_input() {
+        let list = vec![("a", 12u32), ("b", 15u32)];
+        let err = select_from_weighted_excluding(RANDOMNESS1, &list, &[0, 1]).unwrap_err();
+        assert_eq!(err, "All elements are excluded");
+        let err = select_from_weighted_excluding(RANDOMNESS1, &list, &[2]).unwrap_err();
+        assert_eq!(err, "Excluded index 2 is out of bounds");
+        let err = select_from_weighted_excluding(RANDOMNESS1, &Vec::<(&str, u32)>::new(), &[])
+            .unwrap_err();
+        assert_eq!(err, "All elements are excluded");
+
+        let list = vec![("a", 0u32), ("b", 5u32)];
+        let err = select_from_weighted_excluding(RANDOMNESS1, &list, &[1]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn select_from_weighted_excluding_distribution_is_uniform() {
+        use crate::sub_randomness::sub_randomness;
+        use std::collections::HashMap;
+
+        const TEST_SAMPLE_SIZE: usize = 200_000;
+        const ACCURACY: f32 = 0.02;
+        let list = vec![("a", 100u32), ("b", 200u32), ("c", 300u32), ("d", 400u32)];
+        let exclude = [1];
+        // Remaining total weight without "b"
+        let total_weight = 800f32;
+
+        let mut histogram = HashMap::new();
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let selected = select_from_weighted_excluding(subrand, &list, &exclude).unwrap();
+            *histogram.entry(selected).or_insert(0) += 1;
+        }
+
+        assert_eq!(histogram.get("b"), None);
+        for (bin, count) in histogram {
+            let probability = list.iter().find(|e| e.0 == bin).unwrap().1 as f32 / total_weight;
+            let estimated = TEST_SAMPLE_SIZE as f32 * probability;
+            let estimation_min = (estimated * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated * (1_f32 + ACCURACY)) as i32;
+            println!("{bin}: {count}, min: {estimation_min}, max: {estimation_max}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn select_coin_works() {
+        let allocations = vec![
+            ("unois".to_string(), Uint128::new(5_000_000_000)),
+            ("uatom".to_string(), Uint128::new(1_200_000_000)),
+            ("uosmo".to_string(), Uint128::new(3_800_000_000)),
+            ("ujuno".to_string(), Uint128::zero()),
+        ];
+        let weighted: Vec<(String, u128)> = allocations
+            .iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| (denom.clone(), amount.u128()))
+            .collect();
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(50) {
+            let denom = select_coin(randomness, &allocations).unwrap();
+            assert_ne!(denom, "ujuno");
+            assert_eq!(denom, select_from_weighted(randomness, &weighted).unwrap());
+        }
+
+        // Huge allocations
+        let allocations = vec![
+            ("ubig".to_string(), Uint128::MAX - Uint128::one()),
+            ("usmall".to_string(), Uint128::one()),
+        ];
+        assert_eq!(select_coin(RANDOMNESS1, &allocations).unwrap(), "ubig");
+    }
+
+    #[test]
+    fn select_coin_fails_for_invalid_allocations() {
+        let err = select_coin(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+
+        let allocations = vec![
+            ("unois".to_string(), Uint128::zero()),
+            ("uatom".to_string(), Uint128::zero()),
+        ];
+        let err = select_coin(RANDOMNESS1, &allocations).unwrap_err();
+        assert_eq!(err, "Total allocation must not be zero");
+
+        let allocations = vec![
+            ("unois".to_string(), Uint128::MAX),
+            ("uatom".to_string(), Uint128::one()),
+        ];
+        let err = select_coin(RANDOMNESS1, &allocations).unwrap_err();
+        assert_eq!(err, "Total allocation exceeds the Uint128 range");
+    }
+
+    #[test]
+    fn uint256_below_works() {
+        assert_eq!(uint256_below(RANDOMNESS1, Uint256::one()), Uint256::zero());
+        for end in [2u128, 3, 7, 256, 1000, u128::MAX] {
+            let end = Uint256::from(end);
+            for randomness in crate::sub_randomness(RANDOMNESS1).take(50) {
+                assert!(uint256_below(randomness, end) < end);
+            }
+        }
+        let value = uint256_below(RANDOMNESS1, Uint256::MAX);
+        assert!(value > Uint256::from(u128::MAX));
+
+        // All values are hit
+        let mut seen = [false; 5];
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(100) {
+            let value = uint256_below(randomness, Uint256::from(5u32));
+            seen[value.to_be_bytes()[31] as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+
+    #[test]
+    fn select_by_stake_seconds_works() {
+        use std::collections::HashMap;
+
+        let now = 1_700_000_000u64;
+        let stakers = vec![
+            ("small short", Uint128::new(1_000), now - 100),
+            ("small long", Uint128::new(1_000), now - 1_000),
+            ("large long", Uint128::new(10_000), now - 1_000),
+            ("just staked", Uint128::new(1_000_000), now),
+        ];
+        let mut histogram = HashMap::new();
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(10_000) {
+            let winner = select_by_stake_seconds(randomness, &stakers, now).unwrap();
+            *histogram.entry(winner).or_insert(0) += 1;
+        }
+        println!("{histogram:?}");
+        // Weights 1:10:100
+        let small_short = histogram.get("small short").copied().unwrap_or_default();
+        let small_long = histogram["small long"];
+        let large_long = histogram["large long"];
+        assert!(small_short < small_long);
+        assert!(small_long < large_long);
+        assert!(large_long > 8_500);
+        assert_eq!(histogram.get("just staked"), None);
+
+        // Huge stakes do not overflow
+        let stakers = vec![
+            ("whale", Uint128::MAX, 0),
+            ("shrimp", Uint128::new(1), u64::MAX - 1),
+        ];
+        let winner = select_by_stake_seconds(RANDOMNESS1, &stakers, u64::MAX).unwrap();
+        assert_eq!(winner, "whale");
+    }
+
+    #[test]
+    fn select_by_stake_seconds_fails_for_invalid_input() {
+        let err = select_by_stake_seconds::<&str>(RANDOMNESS1, &[], 100).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+
+        let stakers = vec![("a", Uint128::new(5), 50), ("b", Uint128::new(5), 101)];
+        let err = select_by_stake_seconds(RANDOMNESS1, &stakers, 100).unwrap_err();
+        assert_eq!(err, "Stake time must not be later than now");
+
+        let stakers = vec![("a", Uint128::new(5), 100), ("b", Uint128::zero(), 50)];
+        let err = select_by_stake_seconds(RANDOMNESS1, &stakers, 100).unwrap_err();
+        assert_eq!(err, "Total weight must not be zero");
+    }
+
+    #[test]
+    fn select_and_report_works() {
+        let list = vec![("a", 12u32), ("b", 15u32), ("c", 8u32), ("d", 21u32)];
+        for randomness in crate::sub_randomness(RANDOMNESS1).take(20) {
+            let (element, weight, total_weight) = select_and_report(randomness, &list).unwrap();
+            assert_eq!(element, select_from_weighted(randomness, &list).unwrap());
+            assert_eq!(weight, list.iter().find(|e| e.0 == element).unwrap().1);
+            assert_eq!(total_weight, 56);
+        }
+
+        let err = select_and_report(RANDOMNESS1, &Vec::<(&str, u32)>::new()).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+    }
+
+    #[test]
+    fn weighted_draws_with_floor_works() {
+        let list = vec![("a", 1u32), ("b", 10u32), ("c", 100u32), ("d", 1000u32)];
+
+        let draws = weighted_draws_with_floor(RANDOMNESS1, &list, 50, 3).unwrap();
+        assert_eq!(draws.len(), 50);
+        for (element, _) in &list {
+            let count = draws.iter().filter(|d| *d == element).count();
+            assert!(count >= 3, "{element} drawn {count} times");
+        }
+        // The heaviest element gets most of the weighted draws
+        assert!(draws.iter().filter(|d| **d == "d").count() > 30);
+
+        // Floor 0 is purely weighted
+        let draws = weighted_draws_with_floor(RANDOMNESS1, &list, 5, 0).unwrap();
+        assert_eq!(draws.len(), 5);
+
+        // All draws are guaranteed
+        let draws = weighted_draws_with_floor(RANDOMNESS1, &list, 8, 2).unwrap();
+        assert_eq!(draws, vec!["a", "a", "b", "b", "c", "c", "d", "d"]);
+
+        // No draws
+        let draws = weighted_draws_with_floor(RANDOMNESS1, &list, 0, 0).unwrap();
+        assert_eq!(draws, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn weighted_draws_with_floor_fails_for_floor_too_high() {
+        let list = vec![("a", 1u32), ("b", 10u32), ("c", 100u32)];
+        let err = weighted_draws_with_floor(RANDOMNESS1, &list, 8, 3).unwrap_err();
+        assert_eq!(
+            err,
+            "Floor draws for all elements exceed the total number of draws"
+        );
+        let err = weighted_draws_with_floor(RANDOMNESS1, &list, 8, usize::MAX).unwrap_err();
+        assert_eq!(
+            err,
+            "Floor draws for all elements exceed the total number of draws"
+        );
+    }
+
+    #[test]
+    fn weighted_draws_with_floor_fails_for_invalid_list() {
+        let list: Vec<(&str, u32)> = vec![];
+        let err = weighted_draws_with_floor(RANDOMNESS1, &list, 8, 0).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+
+        let list = vec![("a", 1u32), ("b", 0u32)];
+        let err = weighted_draws_with_floor(RANDOMNESS1, &list, 8, 1).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn random_weights_summing_to_works() {
+        for (count, total) in [(1, 1), (1, 100), (3, 3), (4, 100), (10, 11), (50, 1000)] {
+            for randomness in crate::sub_randomness(RANDOMNESS1).take(10) {
+                let weights = random_weights_summing_to(randomness, count, total).unwrap();
+                assert_eq!(weights.len(), count);
+                assert_eq!(weights.iter().sum::<u32>(), total);
+                assert!(weights.iter().all(|w| *w >= 1));
+            }
+        }
+
+        // Large total
+        let weights = random_weights_summing_to(RANDOMNESS1, 5, u32::MAX).unwrap();
+        assert_eq!(
+            weights.iter().map(|w| *w as u64).sum::<u64>(),
+            u32::MAX as u64
+        );
+        assert!(choose_branch(RANDOMNESS1, &weights).is_ok());
+
+        // Not all weights are the same
+        let weights = random_weights_summing_to(RANDOMNESS1, 4, 100).unwrap();
+        assert!(weights.iter().any(|w| *w != 25));
+    }
+
+    #[test]
+    fn random_weights_summing_to_fails_for_invalid_count() {
+        let err = random_weights_summing_to(RANDOMNESS1, 0, 10).unwrap_err();
+        assert_eq!(err, "Count must not be 0");
+        let err = random_weights_summing_to(RANDOMNESS1, 11, 10).unwrap_err();
+        assert_eq!(err, "Count must not exceed the total");
+        let err = random_weights_summing_to(RANDOMNESS1, 1, 0).unwrap_err();
+        assert_eq!(err, "Count must not exceed the total");
+    }
+
+    #[test]
+    fn choose_branch_works() {
+        let index = choose_branch(RANDOMNESS1, &[1]).unwrap();
+        assert_eq!(index, 0);
+
+        let index = choose_branch(RANDOMNESS1, &[1, 5, 4]).unwrap();
+        assert_eq!(index, 2);
+
+        // Same result as select_from_weighted for the same weights
+        let elements: Vec<(char, u32)> = vec![('a', 12), ('b', 15), ('c', 8), ('d', 21)];
+        let weights: Vec<u32> = elements.iter().map(|e| e.1).collect();
+        let index = choose_branch(RANDOMNESS1, &weights).unwrap();
+        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
+        assert_eq!(elements[index].0, picked);
+    }
+
+    #[test]
+    fn choose_branch_fails_on_empty_list() {
+        let err = choose_branch(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+    }
+
+    #[test]
+    fn choose_branch_fails_on_weight_less_than_1() {
+        let err = choose_branch(RANDOMNESS1, &[3, 0, 2]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn choose_branch_fails_with_total_weight_too_high() {
+        let err = choose_branch(RANDOMNESS1, &[u32::MAX, 1]).unwrap_err();
+        assert_eq!(err, "Total weight is greater than maximum value of u32");
+    }
+
+    #[test]
+    fn select_index_from_weighted_with_tiebreak_works() {
+        let weights = [2, 3];
+
+        // Craft a primary randomness that hits ticket 2, i.e. the boundary between index 0 and 1
+        let primary = (0..=255u8)
+            .map(|b| [b; 32])
+            .find(|r| int_in_range(*r, 1u32, 5) == 2)
+            .unwrap();
+        let heads = (0..=255u8)
+            .map(|b| [b; 32])
+            .find(|r| coinflip(*r).is_heads())
+            .unwrap();
+        let tails = (0..=255u8)
+            .map(|b| [b; 32])
+            .find(|r| coinflip(*r).is_tails())
+            .unwrap();
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, heads, &weights).unwrap(),
+            1
+        );
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, tails, &weights).unwrap(),
+            0
+        );
+
+        // The boundary after the last element is shared with the first one
+        let primary = (0..=255u8)
+            .map(|b| [b; 32])
+            .find(|r| int_in_range(*r, 1u32, 5) == 5)
+            .unwrap();
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, heads, &weights).unwrap(),
+            0
+        );
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, tails, &weights).unwrap(),
+            1
+        );
+
+        // Interior tickets are not affected by the secondary randomness
+        let primary = (0..=255u8)
+            .map(|b| [b; 32])
+            .find(|r| int_in_range(*r, 1u32, 5) == 3)
+            .unwrap();
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, heads, &weights).unwrap(),
+            1
+        );
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(primary, tails, &weights).unwrap(),
+            1
+        );
+
+        // A single element is always selected
+        assert_eq!(
+            select_index_from_weighted_with_tiebreak(RANDOMNESS1, heads, &[7]).unwrap(),
+            0
+        );
+
+        // Errors
+        let err = select_index_from_weighted_with_tiebreak(RANDOMNESS1, heads, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err =
+            select_index_from_weighted_with_tiebreak(RANDOMNESS1, heads, &[3, 0]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
 
-    let mut total_weight = W::ZERO;
-    for (_, weight) in list {
-        if *weight == W::ZERO {
-            return Err(String::from("All element weights should be >= 1"));
+    #[test]
+    fn select_index_from_weighted_with_tiebreak_distribution_is_proportional() {
+        use crate::sub_randomness::sub_randomness;
+
+        const TEST_SAMPLE_SIZE: usize = 300_000;
+        const ACCURACY: f32 = 0.02;
+        // Small weights such that many draws land on a boundary
+        let weights = [1u32, 2, 3];
+
+        let mut histogram = [0usize; 3];
+        let mut provider = sub_randomness(RANDOMNESS1);
+        for _ in 0..TEST_SAMPLE_SIZE {
+            let index = select_index_from_weighted_with_tiebreak(
+                provider.provide(),
+                provider.provide(),
+                &weights,
+            )
+            .unwrap();
+            histogram[index] += 1;
+        }
+
+        for (index, count) in histogram.into_iter().enumerate() {
+            let estimated = TEST_SAMPLE_SIZE as f32 * weights[index] as f32 / 6.0;
+            let estimation_min = (estimated * (1_f32 - ACCURACY)) as usize;
+            let estimation_max = (estimated * (1_f32 + ACCURACY)) as usize;
+            println!("{index}: {count}, min: {estimation_min}, max: {estimation_max}");
+            assert!(count >= estimation_min && count <= estimation_max);
         }
-        total_weight = total_weight
-            .checked_add(*weight)
-            .ok_or_else(|| String::from("Total weight is greater than maximum value of u32"))?;
     }
 
-    debug_assert!(
-        total_weight > W::ZERO,
-        "we know we have a non-empty list of non-zero elements"
-    );
+    #[test]
+    fn choose_branch_distribution_is_uniform() {
+        /// This test will generate a huge amount  of subrandomness
+        /// then checks that the distribution is expected within a range of 1%
+        use crate::sub_randomness::sub_randomness;
+        use std::collections::HashMap;
 
-    let r = int_in_range::<W>(randomness, W::ONE, total_weight);
-    let mut weight_sum = W::ZERO;
-    for element in list {
-        weight_sum += element.1;
-        if r <= weight_sum {
-            return Ok(element.0.clone());
+        const TEST_SAMPLE_SIZE: usize = 1_000_000;
+        const ACCURACY: f32 = 0.01;
+
+        let weights: [u32; 4] = [100, 250, 50, 600];
+        let total_weight = weights.iter().sum::<u32>();
+
+        let mut histogram = HashMap::new();
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let index = choose_branch(subrand, &weights).unwrap();
+            let count = histogram.entry(index).or_insert(0);
+            *count += 1;
+        }
+
+        assert_eq!(histogram.len(), weights.len());
+        for (bin, count) in histogram {
+            let probability = weights[bin] as f32 / total_weight as f32;
+            let estimated_count = TEST_SAMPLE_SIZE as f32 * probability;
+            let estimation_min: i32 = (estimated_count * (1_f32 - ACCURACY)) as i32;
+            let estimation_max: i32 = (estimated_count * (1_f32 + ACCURACY)) as i32;
+            println!(
+                "{}: {} (min: {}, max: {})",
+                bin, count, estimation_min, estimation_max
+            );
+            assert!(count >= estimation_min && count <= estimation_max);
         }
     }
-    // This point should never be reached
-    panic!("No element selected")
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::RANDOMNESS1;
+    #[test]
+    fn pity_state_new_works() {
+        let state = PityState::new(vec![10, 1], 1, 2, 20).unwrap();
+        assert_eq!(state.counter(), 0);
 
-    use super::*;
+        let err = PityState::new(vec![], 0, 2, 20).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = PityState::new(vec![10, 0], 1, 2, 20).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+        let err = PityState::new(vec![10, 1], 2, 2, 20).unwrap_err();
+        assert_eq!(err, "Rare index out of bounds");
+    }
 
     #[test]
-    fn select_from_weighted_works() {
-        let elements: Vec<(char, u32)> = vec![('a', 1), ('b', 5), ('c', 4)];
-        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
-        assert_eq!(picked, 'c');
+    fn pity_state_draw_works() {
+        use crate::sub_randomness::sub_randomness;
 
-        // Element type is Clone but not Copy
-        #[derive(PartialEq, Debug, Clone)]
-        struct Color(String);
-        let elements = vec![
-            (Color("red".into()), 12u32),
-            (Color("blue".to_string()), 15u32),
-            (Color("green".to_string()), 8u32),
-            (Color("orange".to_string()), 21u32),
-            (Color("pink".to_string()), 11u32),
-        ];
-        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
-        assert_eq!(picked, Color("orange".to_string()));
+        // Without boost, the rare is guaranteed after 10 misses
+        let mut state = PityState::new(vec![1_000_000, 1], 1, 0, 10).unwrap();
+        let mut provider = sub_randomness(RANDOMNESS1);
+        for i in 0..10 {
+            assert_eq!(state.counter(), i);
+            assert_eq!(state.draw(provider.provide()), 0);
+        }
+        assert_eq!(state.counter(), 10);
+        assert_eq!(state.draw(provider.provide()), 1);
+        assert_eq!(state.counter(), 0);
 
-        // Test for u128
-        let elements = vec![
-            (Color("red".into()), 12u128),
-            (Color("blue".to_string()), 15u128),
-            (Color("green".to_string()), 8u128),
-            (Color("orange".to_string()), 21u128),
-            (Color("pink".to_string()), 11u128),
-        ];
-        let picked = select_from_weighted(RANDOMNESS1, &elements).unwrap();
-        assert_eq!(picked, Color("blue".to_string()));
+        // A high boost makes the rare very likely long before the guarantee
+        let mut state = PityState::new(vec![100, 1], 1, 1_000_000, 1_000).unwrap();
+        assert_eq!(state.draw([0x03; 32]), 0);
+        assert_eq!(state.counter(), 1);
+        assert_eq!(state.draw(provider.provide()), 1);
+        assert_eq!(state.counter(), 0);
+    }
 
-        // Pick from slice
-        let selection = &elements[0..3];
-        let picked = select_from_weighted(RANDOMNESS1, selection).unwrap();
-        assert_eq!(picked, Color("red".to_string()));
+    #[test]
+    fn pity_state_can_be_stored() {
+        use cosmwasm_std::{from_json, to_json_vec};
+
+        let mut state = PityState::new(vec![90, 9, 1], 2, 5, 50).unwrap();
+        state.draw(RANDOMNESS1);
+        let stored = to_json_vec(&state).unwrap();
+        let restored: PityState = from_json(stored).unwrap();
+        assert_eq!(restored, state);
     }
 
     #[test]
-    fn select_from_weighted_fails_on_empty_list() {
-        //This will check that the list is empty
-        let elements: Vec<(i32, u32)> = vec![];
+    fn round_robin_random_works() {
+        use crate::sub_randomness::sub_randomness;
 
-        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+        let err = RoundRobinRandom::<u32>::new(vec![]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
 
-        // Check that the selected element has the expected weight
+        let options = vec!['a', 'b', 'c', 'd', 'e'];
+        let mut bag = RoundRobinRandom::new(options.clone()).unwrap();
+        let mut provider = sub_randomness(RANDOMNESS1);
+
+        assert_eq!(bag.remaining(), 5);
+        let mut cycles = vec![];
+        for _ in 0..3 {
+            let mut cycle: Vec<char> = (0..5).map(|_| bag.next(provider.provide())).collect();
+            assert_eq!(bag.remaining(), 0);
+            cycles.push(cycle.clone());
+            // Every option appears exactly once per cycle
+            cycle.sort();
+            assert_eq!(cycle, options);
+        }
+        // The order differs between cycles
+        assert!(cycles[0] != cycles[1] || cycles[1] != cycles[2]);
+
+        // Refill happens on the next draw
+        bag.next(provider.provide());
+        assert_eq!(bag.remaining(), 4);
+    }
+
+    #[test]
+    fn capped_weighted_pool_works() {
+        use crate::sub_randomness::sub_randomness;
+
+        let err = CappedWeightedPool::new(vec![], 3).unwrap_err();
         assert_eq!(err, "List must not be empty");
+        let err = CappedWeightedPool::new(vec![1, 0], 3).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+
+        // Drain a small pool
+        let weights = vec![1000, 10, 1];
+        let mut pool = CappedWeightedPool::new(weights, 3).unwrap();
+        let mut counts = [0u32; 3];
+        let mut provider = sub_randomness(RANDOMNESS1);
+        while let Some(index) = pool.draw(provider.provide()) {
+            counts[index] += 1;
+            assert!(counts[index] <= 3);
+            assert_eq!(pool.remaining(index), 3 - counts[index]);
+        }
+        assert_eq!(counts, [3, 3, 3]);
+        assert_eq!(pool.draw(provider.provide()), None);
+
+        // Zero cap
+        let mut pool = CappedWeightedPool::new(vec![1, 2], 0).unwrap();
+        assert_eq!(pool.draw(RANDOMNESS1), None);
     }
 
     #[test]
-    fn select_from_weighted_fails_on_element_weight_less_than_1() {
-        let elements: Vec<(i32, u32)> = vec![(1, 5), (2, 4), (-3, 0)];
+    fn draw_winner_with_proof_works() {
+        let entrants = vec![('a', 12u32), ('b', 15), ('c', 8), ('d', 21), ('e', 11)];
+        let (winner, proof) = draw_winner_with_proof(RANDOMNESS1, &entrants).unwrap();
+        assert_eq!(
+            winner,
+            select_from_weighted(RANDOMNESS1, &entrants).unwrap()
+        );
+        assert_eq!(proof.randomness, HexBinary::from(RANDOMNESS1));
+        assert_eq!(proof.total_weight, 67);
+        assert!(proof.drawn_ticket >= 1 && proof.drawn_ticket <= 67);
 
-        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+        // The proof reproduces the winner
+        assert!(verify_winner(&entrants, &proof, &winner));
+        for other in ['a', 'b', 'c', 'd', 'e'] {
+            if other != winner {
+                assert!(!verify_winner(&entrants, &proof, &other));
+            }
+        }
 
-        // Check that the selected element has the expected weight
+        // Tampered proofs and lists are rejected
+        let mut tampered = proof.clone();
+        tampered.drawn_ticket = proof.drawn_ticket % 67 + 1;
+        assert!(!verify_winner(&entrants, &tampered, &winner));
+        let mut tampered = proof.clone();
+        tampered.total_weight += 1;
+        assert!(!verify_winner(&entrants, &tampered, &winner));
+        assert!(!verify_winner(&entrants[1..], &proof, &winner));
+        let mut tampered = proof.clone();
+        tampered.randomness = HexBinary::from(&RANDOMNESS1[..31]);
+        assert!(!verify_winner(&entrants, &tampered, &winner));
+
+        let err = draw_winner_with_proof::<char>(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = draw_winner_with_proof(RANDOMNESS1, &[('a', 0)]).unwrap_err();
         assert_eq!(err, "All element weights should be >= 1");
     }
 
     #[test]
-    fn select_from_weighted_fails_with_total_weight_too_high() {
-        let elements: Vec<(i32, u128)> = vec![(1, u128::MAX), (2, 1)];
+    fn winner_proof_serializes_to_hex() {
+        use cosmwasm_std::{from_json, to_json_string};
 
-        let err = select_from_weighted(RANDOMNESS1, &elements).unwrap_err();
+        let entrants = vec![("alice", 3u32), ("bob", 10)];
+        let (_, proof) = draw_winner_with_proof(RANDOMNESS1, &entrants).unwrap();
+        let json = to_json_string(&proof).unwrap();
+        assert!(json.contains(&format!(
+            r#""randomness":"{}""#,
+            HexBinary::from(RANDOMNESS1).to_hex()
+        )));
+        let restored: WinnerProof = from_json(json).unwrap();
+        assert_eq!(restored, proof);
+    }
 
-        // Check that the selected element has the expected weight
-        assert_eq!(err, "Total weight is greater than maximum value of u32");
+    #[test]
+    fn spin_wheel_works() {
+        use crate::sub_randomness::sub_randomness;
+
+        let segments = vec![('a', 1u32), ('b', 3), ('c', 6), ('d', 2)];
+        let total = 12.0;
+        for subrand in sub_randomness(RANDOMNESS1).take(1000) {
+            let (segment, angle) = spin_wheel(subrand, &segments).unwrap();
+            assert_eq!(segment, select_from_weighted(subrand, &segments).unwrap());
+
+            // The angle lies within the selected segment's arc
+            let index = segments.iter().position(|s| s.0 == segment).unwrap();
+            let start: u32 = segments[..index].iter().map(|s| s.1).sum();
+            let end = start + segments[index].1;
+            let start_angle = start as f64 / total * 360.0;
+            let end_angle = end as f64 / total * 360.0;
+            assert!(angle >= start_angle && angle < end_angle);
+            assert!((0.0..360.0).contains(&angle));
+        }
+
+        // Single segment
+        let (segment, angle) = spin_wheel(RANDOMNESS1, &[("all", 7u32)]).unwrap();
+        assert_eq!(segment, "all");
+        assert!((0.0..360.0).contains(&angle));
+
+        let err = spin_wheel::<char>(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
     }
 
     #[test]
-    fn select_from_weighted_distribution_is_uniform() {
-        /// This test will generate a huge amount  of subrandomness
-        /// then checks that the distribution is expected within a range of 1%
+    fn random_discount_works() {
+        let tiers = [(5u8, 70u32), (10, 25), (20, 5)];
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            let discount = random_discount(subrand, &tiers).unwrap();
+            assert!([5, 10, 20].contains(&discount));
+            assert_eq!(discount, select_from_weighted(subrand, &tiers).unwrap());
+        }
+
+        assert_eq!(random_discount(RANDOMNESS1, &[(0, 1)]).unwrap(), 0);
+        assert_eq!(random_discount(RANDOMNESS1, &[(100, 1)]).unwrap(), 100);
+    }
+
+    #[test]
+    fn random_discount_fails_for_invalid_tiers() {
+        let err = random_discount(RANDOMNESS1, &[(5, 70), (101, 30)]).unwrap_err();
+        assert_eq!(err, "Discount of 101% exceeds 100%");
+
+        let err = random_discount(RANDOMNESS1, &[(5, 70), (10, 0)]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+
+        let err = random_discount(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+    }
+
+    #[test]
+    fn random_discount_distribution_matches_tiers() {
         use crate::sub_randomness::sub_randomness;
         use std::collections::HashMap;
 
-        const TEST_SAMPLE_SIZE: usize = 1_000_000;
-        const ACCURACY: f32 = 0.01;
-        // This test needs the sum of the weights to be equal to 1.
-        // Although the function should work as expected for weights that do not equal 1
-        let elements: Vec<(String, u32)> = vec![
-            (String::from("a"), 100),
-            (String::from("b"), 200),
-            (String::from("c"), 30),
-            (String::from("d"), 70),
-            (String::from("e"), 600),
+        const TEST_SAMPLE_SIZE: usize = 300_000;
+        const ACCURACY: f32 = 0.03;
+        let tiers = [(5u8, 70u32), (10, 25), (20, 5)];
+
+        let mut histogram = HashMap::new();
+        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            let discount = random_discount(subrand, &tiers).unwrap();
+            *histogram.entry(discount).or_insert(0) += 1;
+        }
+
+        for (discount, weight) in tiers {
+            let count = histogram[&discount];
+            let estimated = TEST_SAMPLE_SIZE as f32 * weight as f32 / 100.0;
+            let estimation_min = (estimated * (1_f32 - ACCURACY)) as i32;
+            let estimation_max = (estimated * (1_f32 + ACCURACY)) as i32;
+            println!("{discount}%: {count}, min: {estimation_min}, max: {estimation_max}");
+            assert!(count >= estimation_min && count <= estimation_max);
+        }
+    }
+
+    #[test]
+    fn random_subset_in_weight_band_works() {
+        let items = vec![
+            ("sword", 30u32),
+            ("shield", 25),
+            ("potion", 5),
+            ("ring", 20),
+            ("gem", 15),
+            ("scroll", 10),
         ];
-        let total_weight = elements.iter().map(|element| element.1).sum::<u32>();
-        println!("total weight: {}", total_weight);
+        let weight_of = |name: &&str| items.iter().find(|(n, _)| n == name).unwrap().1;
 
-        let mut result = vec![];
+        // The band is wide enough to always succeed
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(200) {
+            let subset = random_subset_in_weight_band(subrand, &items, 40, 69).unwrap();
+            let total: u32 = subset.iter().map(weight_of).sum();
+            assert!((40..=69).contains(&total), "{total}");
+            // Every item at most once
+            for (i, item) in subset.iter().enumerate() {
+                assert!(!subset[..i].contains(item));
+            }
+        }
 
-        for subrand in sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
-            result.push(select_from_weighted(subrand, &elements).unwrap());
+        // Different randomness leads to different subsets
+        let subsets: Vec<Vec<&str>> = crate::sub_randomness(RANDOMNESS1)
+            .take(20)
+            .map(|subrand| random_subset_in_weight_band(subrand, &items, 40, 69).unwrap())
+            .collect();
+        assert!(subsets.iter().any(|subset| *subset != subsets[0]));
+
+        // Empty band at 0
+        let subset = random_subset_in_weight_band(RANDOMNESS1, &items, 0, 0).unwrap();
+        assert!(subset.is_empty());
+        let subset = random_subset_in_weight_band::<&str>(RANDOMNESS1, &[], 0, 10).unwrap();
+        assert!(subset.is_empty());
+
+        // All items
+        let subset = random_subset_in_weight_band(RANDOMNESS1, &items, 105, 105).unwrap();
+        assert_eq!(subset.len(), items.len());
+
+        // Large weights do not overflow
+        let items = vec![("a", u32::MAX), ("b", u32::MAX)];
+        let subset = random_subset_in_weight_band(RANDOMNESS1, &items, 1, u32::MAX).unwrap();
+        assert_eq!(subset.len(), 1);
+    }
+
+    #[test]
+    fn random_subset_in_weight_band_fails_for_invalid_input() {
+        let items = vec![("sword", 30u32), ("shield", 25)];
+
+        let err = random_subset_in_weight_band(RANDOMNESS1, &items, 50, 40).unwrap_err();
+        assert_eq!(err, "Minimum weight must not exceed maximum weight");
+
+        let err = random_subset_in_weight_band(RANDOMNESS1, &items, 40, 50).unwrap_err();
+        assert_eq!(err, "No subset with a total weight in [40, 50] found");
+
+        let err = random_subset_in_weight_band(RANDOMNESS1, &items, 60, 100).unwrap_err();
+        assert_eq!(err, "No subset with a total weight in [60, 100] found");
+
+        let err =
+            random_subset_in_weight_band(RANDOMNESS1, &[("a", 10), ("b", 0)], 5, 10).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn select_excluding_previous_works() {
+        let list = vec![("alice", 10u32), ("bob", 20u32), ("carol", 30u32)];
+
+        // The previous winner is skipped when alternatives exist
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(200) {
+            for previous in ["alice", "bob", "carol"] {
+                let winner = select_excluding_previous(subrand, &list, Some(&previous)).unwrap();
+                assert_ne!(winner, previous);
+            }
         }
 
-        let mut histogram = HashMap::new();
+        // No previous winner or unknown previous winner
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(20) {
+            let expected = select_from_weighted(subrand, &list).unwrap();
+            assert_eq!(
+                select_excluding_previous(subrand, &list, None).unwrap(),
+                expected
+            );
+            assert_eq!(
+                select_excluding_previous(subrand, &list, Some(&"dave")).unwrap(),
+                expected
+            );
+        }
 
-        for element in result {
-            let count = histogram.entry(element).or_insert(0);
-            *count += 1;
+        // Only entrant
+        let list = vec![("alice", 10u32)];
+        let winner = select_excluding_previous(RANDOMNESS1, &list, Some(&"alice")).unwrap();
+        assert_eq!(winner, "alice");
+        let list = vec![("alice", 10u32), ("alice", 5u32)];
+        let winner = select_excluding_previous(RANDOMNESS1, &list, Some(&"alice")).unwrap();
+        assert_eq!(winner, "alice");
+    }
+
+    #[test]
+    fn select_excluding_previous_fails_for_invalid_list() {
+        let err = select_excluding_previous::<&str>(RANDOMNESS1, &[], Some(&"alice")).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = select_excluding_previous::<&str>(RANDOMNESS1, &[], None).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+
+        let list = vec![("alice", 10u32), ("bob", 0u32)];
+        let err = select_excluding_previous(RANDOMNESS1, &list, Some(&"alice")).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn select_trait_works() {
+        let options = vec![("cap", 60u32), ("crown", 5u32), ("beanie", 35u32)];
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            let (selected, rarity) = select_trait(subrand, &options).unwrap();
+            assert_eq!(selected, select_from_weighted(subrand, &options).unwrap());
+            // The rarity is the weight fraction of the selected option
+            let weight = options.iter().find(|(o, _)| *o == selected).unwrap().1;
+            assert_eq!(rarity, Decimal::from_ratio(weight, 100u32));
         }
 
-        // This will assert on all the elements of the data 1 by 1 and check if their occurence is within the 1% expected range
-        for (bin, count) in histogram {
-            let probability = elements.iter().find(|e| e.0 == bin).map(|e| e.1).unwrap() as f32
-                / total_weight as f32;
-            let estimated_count_for_uniform_distribution = TEST_SAMPLE_SIZE as f32 * probability;
-            let estimation_min: i32 =
-                (estimated_count_for_uniform_distribution * (1_f32 - ACCURACY)) as i32;
-            let estimation_max: i32 =
-                (estimated_count_for_uniform_distribution * (1_f32 + ACCURACY)) as i32;
-            println!(
-                "estimation {}, max: {}, min: {}",
-                estimated_count_for_uniform_distribution, estimation_max, estimation_min
+        let (selected, rarity) = select_trait(RANDOMNESS1, &[("only", 7u32)]).unwrap();
+        assert_eq!(selected, "only");
+        assert_eq!(rarity, Decimal::one());
+
+        let (_, rarity) = select_trait(RANDOMNESS1, &[("a", 1u32), ("b", 1), ("c", 1)]).unwrap();
+        assert_eq!(rarity, Decimal::from_ratio(1u32, 3u32));
+    }
+
+    #[test]
+    fn select_trait_fails_for_invalid_options() {
+        let err = select_trait::<&str>(RANDOMNESS1, &[]).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = select_trait(RANDOMNESS1, &[("a", 1u32), ("b", 0)]).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+    }
+
+    #[test]
+    fn loot_table_works_for_flat_table() {
+        let table = LootTable::new(vec![
+            (LootEntry::Item("gold"), 80u32),
+            (LootEntry::Item("potion"), 15),
+            (LootEntry::Item("sword"), 5),
+        ]);
+        let weights = [("gold", 80u32), ("potion", 15), ("sword", 5)];
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(100) {
+            let loot = table.roll(subrand).unwrap();
+            // One level uses the first sub-randomness
+            let first = crate::sub_randomness(subrand).provide();
+            assert_eq!(loot, select_from_weighted(first, &weights).unwrap());
+        }
+    }
+
+    #[test]
+    fn loot_table_works_for_nested_table() {
+        let gems = LootTable::new(vec![
+            (LootEntry::Item("ruby"), 1u32),
+            (LootEntry::Item("emerald"), 1),
+        ]);
+        let table = LootTable::new(vec![
+            (LootEntry::Item("gold"), 1u32),
+            (LootEntry::Table(Box::new(gems)), 1),
+        ]);
+
+        let mut histogram = std::collections::HashMap::new();
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(10_000) {
+            *histogram.entry(table.roll(subrand).unwrap()).or_insert(0) += 1;
+        }
+        assert_eq!(histogram.len(), 3);
+        // gold: 50%, ruby: 25%, emerald: 25%
+        assert!(histogram["gold"] > 4_700 && histogram["gold"] < 5_300);
+        assert!(histogram["ruby"] > 2_300 && histogram["ruby"] < 2_700);
+        assert!(histogram["emerald"] > 2_300 && histogram["emerald"] < 2_700);
+
+        // Only a nested table
+        let inner = LootTable::new(vec![(LootEntry::Item(7u8), 3u32)]);
+        let outer = LootTable::new(vec![(LootEntry::Table(Box::new(inner)), 1u32)]);
+        assert_eq!(outer.roll(RANDOMNESS1).unwrap(), 7);
+    }
+
+    #[test]
+    fn loot_table_fails_for_invalid_tables() {
+        let empty = LootTable::<u8>::new(vec![]);
+        assert_eq!(
+            empty.roll(RANDOMNESS1).unwrap_err(),
+            "List must not be empty"
+        );
+
+        let nested = LootTable::new(vec![(LootEntry::Table(Box::new(empty)), 1u32)]);
+        assert_eq!(
+            nested.roll(RANDOMNESS1).unwrap_err(),
+            "List must not be empty"
+        );
+
+        let zero = LootTable::new(vec![(LootEntry::Item(1u8), 0u32)]);
+        assert_eq!(
+            zero.roll(RANDOMNESS1).unwrap_err(),
+            "All element weights should be >= 1"
+        );
+
+        // Nesting depth
+        let mut table = LootTable::new(vec![(LootEntry::Item(1u8), 1u32)]);
+        for _ in 1..MAX_LOOT_TABLE_DEPTH {
+            table = LootTable::new(vec![(LootEntry::Table(Box::new(table)), 1u32)]);
+        }
+        assert_eq!(table.roll(RANDOMNESS1).unwrap(), 1);
+        let table = LootTable::new(vec![(LootEntry::Table(Box::new(table)), 1u32)]);
+        assert_eq!(
+            table.roll(RANDOMNESS1).unwrap_err(),
+            "Loot table nesting exceeds the maximum depth of 16"
+        );
+    }
+
+    #[test]
+    fn loot_table_serializes() {
+        use cosmwasm_std::{from_json, to_json_vec};
+
+        let gems = LootTable::new(vec![(LootEntry::Item("ruby".to_string()), 1u32)]);
+        let table = LootTable::new(vec![
+            (LootEntry::Item("gold".to_string()), 9u32),
+            (LootEntry::Table(Box::new(gems)), 1),
+        ]);
+        let json = to_json_vec(&table).unwrap();
+        let restored: LootTable<String> = from_json(json).unwrap();
+        assert_eq!(restored, table);
+    }
+
+    #[test]
+    fn systematic_sample_works() {
+        assert_eq!(
+            systematic_sample(RANDOMNESS1, &[7], 3).unwrap(),
+            vec![0, 0, 0]
+        );
+        assert_eq!(
+            systematic_sample(RANDOMNESS1, &[1, 2], 0).unwrap(),
+            Vec::<usize>::new()
+        );
+        // Equal weights and k = len select every index once
+        assert_eq!(
+            systematic_sample(RANDOMNESS1, &[3, 3, 3, 3], 4).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+
+        let weights = [40u32, 25, 20, 10, 5];
+        let total: u32 = weights.iter().sum();
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(200) {
+            for k in [1usize, 3, 7, 20, 100] {
+                let selected = systematic_sample(subrand, &weights, k).unwrap();
+                assert_eq!(selected.len(), k);
+                assert!(selected.windows(2).all(|pair| pair[0] <= pair[1]));
+
+                // Each count is the expected count rounded down or up
+                for (index, weight) in weights.iter().enumerate() {
+                    let count = selected.iter().filter(|i| **i == index).count();
+                    let floor = *weight as usize * k / total as usize;
+                    assert!(count == floor || count == floor + 1, "{selected:?}");
+                }
+            }
+        }
+
+        // Deterministic
+        assert_eq!(
+            systematic_sample(RANDOMNESS1, &weights, 7).unwrap(),
+            systematic_sample(RANDOMNESS1, &weights, 7).unwrap()
+        );
+    }
+
+    #[test]
+    fn systematic_sample_is_proportional() {
+        const TEST_SAMPLE_SIZE: usize = 2_000;
+        const K: usize = 3;
+
+        let weights = [60u32, 30, 9, 1];
+        let mut histogram = [0usize; 4];
+        for subrand in crate::sub_randomness(RANDOMNESS1).take(TEST_SAMPLE_SIZE) {
+            for index in systematic_sample(subrand, &weights, K).unwrap() {
+                histogram[index] += 1;
+            }
+        }
+        // Higher weights get proportionally more selections
+        assert!(histogram[0] > histogram[1]);
+        assert!(histogram[1] > histogram[2]);
+        assert!(histogram[2] > histogram[3]);
+        for (count, weight) in histogram.iter().zip(weights) {
+            let expected = (TEST_SAMPLE_SIZE * K * weight as usize / 100) as f32;
+            let tolerance = 0.05 * expected + 20.0;
+            assert!(
+                (*count as f32 - expected).abs() <= tolerance,
+                "{histogram:?}"
             );
-            println!("{}: {}", bin, count);
-            assert!(count >= estimation_min && count <= estimation_max);
         }
     }
+
+    #[test]
+    fn systematic_sample_fails_for_invalid_weights() {
+        let err = systematic_sample(RANDOMNESS1, &[], 2).unwrap_err();
+        assert_eq!(err, "List must not be empty");
+        let err = systematic_sample(RANDOMNESS1, &[3, 0, 2], 2).unwrap_err();
+        assert_eq!(err, "All element weights should be >= 1");
+        let err = systematic_sample(RANDOMNESS1, &[u32::MAX, 1], 2).unwrap_err();
+        assert_eq!(err, "Total weight is greater than maximum value of u32");
+    }
 }
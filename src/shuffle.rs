@@ -1,4 +1,5 @@
-use rand::Rng;
+use cosmwasm_schema::cw_serde;
+use rand::{Rng, RngCore};
 
 use crate::prng::make_prng;
 
@@ -62,14 +63,361 @@ use crate::prng::make_prng;
 /// assert_ne!(shuffled, original);
 /// ```
 pub fn shuffle<T>(randomness: [u8; 32], mut data: Vec<T>) -> Vec<T> {
+    shuffle_in_place(randomness, &mut data);
+    data
+}
+
+/// Shuffles a slice in place, e.g. a part of a larger buffer or an array.
+///
+/// The result is the same as the result of [`shuffle`] for the same randomness and data.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, shuffle, shuffle_in_place};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut deck = [1, 2, 3, 4, 5, 6, 7, 8];
+/// // Shuffle the top half only
+/// shuffle_in_place(randomness, &mut deck[..4]);
+/// assert_eq!(deck[..4], shuffle(randomness, vec![1, 2, 3, 4]));
+/// assert_eq!(deck[4..], [5, 6, 7, 8]);
+/// ```
+pub fn shuffle_in_place<T>(randomness: [u8; 32], data: &mut [T]) {
     let mut rng = make_prng(randomness);
     for i in (1..data.len()).rev() {
         let j = rng.gen_range(0..=i);
         data.swap(i, j);
     }
+}
+
+/// Restores the original order of data that was shuffled with [`shuffle`] and the same
+/// randomness, i.e. `unshuffle(r, shuffle(r, data)) == data`.
+///
+/// The swaps of the Fisher-Yates shuffle are reconstructed from the randomness and undone
+/// in reverse order.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, shuffle, unshuffle};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let original = vec!["bob", "mary", "su", "marc"];
+/// let shuffled = shuffle(randomness, original.clone());
+/// assert_eq!(unshuffle(randomness, shuffled), original);
+/// ```
+pub fn unshuffle<T>(randomness: [u8; 32], mut shuffled: Vec<T>) -> Vec<T> {
+    let mut rng = make_prng(randomness);
+    let swaps: Vec<(usize, usize)> = (1..shuffled.len())
+        .rev()
+        .map(|i| (i, rng.gen_range(0..=i)))
+        .collect();
+    for (i, j) in swaps.into_iter().rev() {
+        shuffled.swap(i, j);
+    }
+    shuffled
+}
+
+/// Shuffles a vector while keeping the elements at the `pinned` indices in place.
+///
+/// The remaining elements are shuffled among the remaining positions using the
+/// Fisher-Yates algorithm.
+///
+/// Panics if a pinned index is out of range.
+///
+/// ## Example
+///
+/// Keep the banner first and the footer last:
+///
+/// ```
+/// use nois::{randomness_from_str, shuffle_pinned};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let data = vec!["banner", "a", "b", "c", "d", "footer"];
+/// let shuffled = shuffle_pinned(randomness, data, &[0, 5]);
+/// assert_eq!(shuffled[0], "banner");
+/// assert_eq!(shuffled[5], "footer");
+/// ```
+pub fn shuffle_pinned<T>(randomness: [u8; 32], mut data: Vec<T>, pinned: &[usize]) -> Vec<T> {
+    if pinned.iter().any(|index| *index >= data.len()) {
+        panic!("pinned index out of range");
+    }
+    let free: Vec<usize> = (0..data.len())
+        .filter(|index| !pinned.contains(index))
+        .collect();
+
+    let mut rng = make_prng(randomness);
+    for i in (1..free.len()).rev() {
+        let j = rng.gen_range(0..=i);
+        data.swap(free[i], free[j]);
+    }
     data
 }
 
+/// Puts tied elements (e.g. players with the same score) into a fair random order.
+///
+/// This is a [`shuffle`] intended for tie-breaking. The randomness used is returned
+/// alongside the result as a tie-break proof. Anyone can verify the ordering by
+/// shuffling the tied elements in their original order with the proof.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{break_ties, randomness_from_str, shuffle};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Three players with a score of 42
+/// let tied = vec!["alice", "bob", "carol"];
+/// let (ranking, proof) = break_ties(randomness, tied.clone());
+/// assert_eq!(ranking, vec!["bob", "carol", "alice"]);
+///
+/// // Verify the ranking
+/// assert_eq!(shuffle(proof, tied), ranking);
+/// ```
+pub fn break_ties<T>(randomness: [u8; 32], tied: Vec<T>) -> (Vec<T>, [u8; 32]) {
+    (shuffle(randomness, tied), randomness)
+}
+
+/// Returns a reproducible random ordering of the items, e.g. for replaying IBC packet
+/// acknowledgements in a stable order seeded by a beacon.
+///
+/// This is the same as [`shuffle`]. The ordering is guaranteed to be stable for a given
+/// randomness and input within a version of this library. If orderings need to be
+/// reproduced at a later point in time, pin the exact version of nois.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{deterministic_order, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let acks = vec!["ack-1", "ack-2", "ack-3", "ack-4"];
+/// let ordered = deterministic_order(randomness, acks.clone());
+/// assert_eq!(ordered, vec!["ack-2", "ack-4", "ack-3", "ack-1"]);
+///
+/// // Replaying gives the same order
+/// assert_eq!(deterministic_order(randomness, acks), ordered);
+/// ```
+pub fn deterministic_order<T>(randomness: [u8; 32], items: Vec<T>) -> Vec<T> {
+    shuffle(randomness, items)
+}
+
+/// Verifies that `claimed` is the result of shuffling `original` with the given randomness.
+///
+/// Use this to resolve disputes about a posted shuffle. Returns false if the lengths differ.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, shuffle, verify_shuffle};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let original = vec![1, 2, 3, 4];
+/// assert!(verify_shuffle(randomness, &original, &[2, 4, 3, 1]));
+/// assert!(!verify_shuffle(randomness, &original, &[1, 2, 3, 4]));
+/// ```
+pub fn verify_shuffle<T: Eq + Clone>(randomness: [u8; 32], original: &[T], claimed: &[T]) -> bool {
+    if original.len() != claimed.len() {
+        return false;
+    }
+    shuffle(randomness, original.to_vec()) == claimed
+}
+
+/// A cursor to sample elements without replacement across multiple calls.
+///
+/// The elements are shuffled once on creation. Pages are then taken from the shuffled
+/// order, such that no element is returned twice until the cursor is exhausted.
+/// The cursor can be serialized to contract storage in order to continue paging
+/// in a later transaction.
+///
+/// ## Example
+///
+/// Show 2 random items per page without repeats:
+///
+/// ```
+/// use nois::{randomness_from_str, ShuffleCursor};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut cursor = ShuffleCursor::new(randomness, vec![1, 2, 3, 4, 5]);
+/// assert_eq!(cursor.next_page(2), vec![2, 4]);
+/// assert_eq!(cursor.next_page(2), vec![5, 1]);
+/// assert_eq!(cursor.next_page(2), vec![3]);
+/// assert!(cursor.is_exhausted());
+/// assert_eq!(cursor.next_page(2), Vec::<i32>::new());
+/// ```
+#[cw_serde]
+pub struct ShuffleCursor<T> {
+    order: Vec<T>,
+    position: usize,
+}
+
+impl<T: Clone> ShuffleCursor<T> {
+    /// Creates a cursor over the shuffled elements.
+    pub fn new(randomness: [u8; 32], data: Vec<T>) -> Self {
+        Self {
+            order: shuffle(randomness, data),
+            position: 0,
+        }
+    }
+
+    /// Returns the next `n` elements. If fewer than `n` elements are remaining,
+    /// all remaining elements are returned.
+    pub fn next_page(&mut self, n: usize) -> Vec<T> {
+        let end = self.position.saturating_add(n).min(self.order.len());
+        let page = self.order[self.position..end].to_vec();
+        self.position = end;
+        page
+    }
+
+    /// The number of elements not yet returned.
+    pub fn remaining(&self) -> usize {
+        self.order.len() - self.position
+    }
+
+    /// True if all elements have been returned.
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+/// A format-preserving permutation of the integers in \[0, domain_size), e.g. to map
+/// NFT token IDs to random looking positions without materializing a shuffled list.
+///
+/// This is a balanced Feistel network over the smallest even number of bits that covers
+/// the domain, combined with cycle walking to stay within the domain. The round keys are
+/// derived from the randomness. Each call costs `O(rounds)` on average, independent of
+/// the domain size.
+///
+/// The permutation is not uniformly distributed over all permutations of the domain and
+/// not suitable for cryptographic purposes. Use at least 4 rounds for a well mixed output.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, FeistelPermutation};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let permutation = FeistelPermutation::new(randomness, 1_000_000, 6);
+/// let position = permutation.encrypt(42);
+/// assert!(position < 1_000_000);
+/// assert_eq!(permutation.decrypt(position), 42);
+/// ```
+#[cw_serde]
+pub struct FeistelPermutation {
+    domain_size: u64,
+    half_bits: u32,
+    keys: Vec<u64>,
+}
+
+impl FeistelPermutation {
+    /// Creates a permutation of \[0, domain_size) with the given number of Feistel rounds.
+    ///
+    /// Panics if `domain_size` or `rounds` is 0.
+    pub fn new(randomness: [u8; 32], domain_size: u64, rounds: usize) -> Self {
+        if domain_size == 0 {
+            panic!("domain size must not be 0");
+        }
+        if rounds == 0 {
+            panic!("rounds must be at least 1");
+        }
+        // Bits needed to represent domain_size - 1, rounded up to an even number (at least 2)
+        let bits = 64 - (domain_size - 1).leading_zeros();
+        let half_bits = bits.div_ceil(2).max(1);
+        let mut rng = make_prng(randomness);
+        let keys = (0..rounds).map(|_| rng.next_u64()).collect();
+        Self {
+            domain_size,
+            half_bits,
+            keys,
+        }
+    }
+
+    /// The size of the domain [0, domain_size) that is permuted.
+    pub fn domain_size(&self) -> u64 {
+        self.domain_size
+    }
+
+    /// Maps `value` to its position in the permutation.
+    ///
+    /// Panics if `value` is not in the domain.
+    pub fn encrypt(&self, value: u64) -> u64 {
+        self.assert_in_domain(value);
+        let mut out = self.permute(value);
+        while out >= self.domain_size {
+            out = self.permute(out);
+        }
+        out
+    }
+
+    /// Maps a position back to its value. This is the inverse of [`encrypt`](Self::encrypt).
+    ///
+    /// Panics if `position` is not in the domain.
+    pub fn decrypt(&self, position: u64) -> u64 {
+        self.assert_in_domain(position);
+        let mut out = self.unpermute(position);
+        while out >= self.domain_size {
+            out = self.unpermute(out);
+        }
+        out
+    }
+
+    fn assert_in_domain(&self, value: u64) {
+        if value >= self.domain_size {
+            panic!(
+                "value {value} is out of the domain [0, {})",
+                self.domain_size
+            );
+        }
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.half_bits)
+    }
+
+    /// One pass through the Feistel network over 2 * half_bits bits
+    fn permute(&self, value: u64) -> u64 {
+        let mask = self.mask();
+        let mut left = value >> self.half_bits;
+        let mut right = value & mask;
+        for key in &self.keys {
+            let next = left ^ (round_function(*key, right) & mask);
+            left = right;
+            right = next;
+        }
+        (left << self.half_bits) | right
+    }
+
+    /// The inverse of [`permute`](Self::permute)
+    fn unpermute(&self, value: u64) -> u64 {
+        let mask = self.mask();
+        let mut left = value >> self.half_bits;
+        let mut right = value & mask;
+        for key in self.keys.iter().rev() {
+            let previous = right ^ (round_function(*key, left) & mask);
+            right = left;
+            left = previous;
+        }
+        (left << self.half_bits) | right
+    }
+}
+
+/// The round function of [`FeistelPermutation`], based on the SplitMix64 finalizer
+fn round_function(key: u64, value: u64) -> u64 {
+    let mut z = value ^ key;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::RANDOMNESS1;
@@ -93,6 +441,175 @@ mod tests {
         assert_ne!(shuffled, vec![1, 2, 3, 4]);
     }
 
+    #[test]
+    fn shuffle_in_place_works() {
+        let mut data: [u32; 0] = [];
+        shuffle_in_place(RANDOMNESS1, &mut data);
+
+        let mut data = [5];
+        shuffle_in_place(RANDOMNESS1, &mut data);
+        assert_eq!(data, [5]);
+
+        // Same as shuffle
+        for len in [2usize, 3, 10, 100] {
+            let mut data: Vec<usize> = (0..len).collect();
+            shuffle_in_place(RANDOMNESS1, &mut data);
+            assert_eq!(data, shuffle(RANDOMNESS1, (0..len).collect()));
+        }
+
+        // Only the given part of a buffer is shuffled
+        let mut buffer: [u8; 12] = core::array::from_fn(|i| i as u8);
+        shuffle_in_place(RANDOMNESS1, &mut buffer[4..8]);
+        assert_eq!(buffer[..4], [0, 1, 2, 3]);
+        assert_eq!(buffer[8..], [8, 9, 10, 11]);
+        assert_eq!(buffer[4..8], shuffle(RANDOMNESS1, vec![4, 5, 6, 7]));
+    }
+
+    #[test]
+    fn shuffle_pinned_works() {
+        let data: Vec<u32> = (0..20).collect();
+        let pinned = [0, 3, 4, 19];
+        let shuffled = shuffle_pinned(RANDOMNESS1, data.clone(), &pinned);
+
+        // Pinned elements stay
+        for index in pinned {
+            assert_eq!(shuffled[index], data[index]);
+        }
+        // Others are permuted among themselves
+        let mut sorted = shuffled.clone();
+        sorted.sort();
+        assert_eq!(sorted, data);
+        assert_ne!(shuffled, data);
+
+        // Without pinned elements this is a regular shuffle
+        assert_eq!(
+            shuffle_pinned(RANDOMNESS1, data.clone(), &[]),
+            shuffle(RANDOMNESS1, data.clone())
+        );
+
+        // All pinned
+        let all: Vec<usize> = (0..20).collect();
+        assert_eq!(shuffle_pinned(RANDOMNESS1, data.clone(), &all), data);
+
+        // Duplicate pinned indices
+        let shuffled = shuffle_pinned(RANDOMNESS1, data.clone(), &[2, 2]);
+        assert_eq!(shuffled[2], 2);
+
+        // Empty
+        assert_eq!(
+            shuffle_pinned(RANDOMNESS1, Vec::<u32>::new(), &[]),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    #[should_panic = "pinned index out of range"]
+    fn shuffle_pinned_panics_for_index_out_of_range() {
+        shuffle_pinned(RANDOMNESS1, vec![1, 2, 3], &[3]);
+    }
+
+    #[test]
+    fn break_ties_works() {
+        let tied: Vec<i32> = vec![];
+        let (ranking, proof) = break_ties(RANDOMNESS1, tied);
+        assert_eq!(ranking, Vec::<i32>::new());
+        assert_eq!(proof, RANDOMNESS1);
+
+        let tied = vec![5];
+        let (ranking, _proof) = break_ties(RANDOMNESS1, tied);
+        assert_eq!(ranking, vec![5]);
+
+        // Reproducible from the proof
+        let tied = vec!["a", "b", "c", "d", "e"];
+        let (ranking, proof) = break_ties(RANDOMNESS1, tied.clone());
+        assert_eq!(ranking.len(), 5);
+        assert_eq!(shuffle(proof, tied.clone()), ranking);
+        let (ranking2, proof2) = break_ties(proof, tied);
+        assert_eq!(ranking2, ranking);
+        assert_eq!(proof2, proof);
+    }
+
+    #[test]
+    fn deterministic_order_works() {
+        let items: Vec<u32> = (0..20).collect();
+        let first = deterministic_order(RANDOMNESS1, items.clone());
+        for _ in 0..5 {
+            assert_eq!(deterministic_order(RANDOMNESS1, items.clone()), first);
+        }
+        assert_eq!(first, shuffle(RANDOMNESS1, items.clone()));
+        assert_ne!(first, items);
+    }
+
+    #[test]
+    fn verify_shuffle_works() {
+        let original = vec!["a", "b", "c", "d", "e", "f"];
+        let shuffled = shuffle(RANDOMNESS1, original.clone());
+
+        // Correct claim
+        assert!(verify_shuffle(RANDOMNESS1, &original, &shuffled));
+
+        // Tampered claim
+        let mut tampered = shuffled.clone();
+        tampered.swap(0, 1);
+        assert!(!verify_shuffle(RANDOMNESS1, &original, &tampered));
+
+        // Claim for different randomness
+        assert!(!verify_shuffle([0x11; 32], &original, &shuffled));
+
+        // Wrong length
+        assert!(!verify_shuffle(RANDOMNESS1, &original, &shuffled[1..]));
+        let mut longer = shuffled.clone();
+        longer.push("g");
+        assert!(!verify_shuffle(RANDOMNESS1, &original, &longer));
+
+        // Empty
+        assert!(verify_shuffle::<u8>(RANDOMNESS1, &[], &[]));
+    }
+
+    #[test]
+    fn shuffle_cursor_works() {
+        let data: Vec<u32> = (0..25).collect();
+        let mut cursor = ShuffleCursor::new(RANDOMNESS1, data.clone());
+        assert_eq!(cursor.remaining(), 25);
+
+        // Page through to exhaustion
+        let mut seen = vec![];
+        while !cursor.is_exhausted() {
+            let page = cursor.next_page(10);
+            assert!(!page.is_empty() && page.len() <= 10);
+            seen.extend(page);
+        }
+        assert_eq!(cursor.remaining(), 0);
+        assert_eq!(cursor.next_page(10), Vec::<u32>::new());
+
+        // The pages form a single shuffle without repeats
+        assert_eq!(seen, shuffle(RANDOMNESS1, data.clone()));
+        let mut sorted = seen.clone();
+        sorted.sort();
+        assert_eq!(sorted, data);
+
+        // Empty input
+        let mut cursor = ShuffleCursor::new(RANDOMNESS1, Vec::<u32>::new());
+        assert!(cursor.is_exhausted());
+        assert_eq!(cursor.next_page(3), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn shuffle_cursor_can_be_stored() {
+        use cosmwasm_std::{from_json, to_json_vec};
+
+        let mut cursor = ShuffleCursor::new(RANDOMNESS1, vec![1, 2, 3, 4, 5, 6]);
+        let first = cursor.next_page(2);
+
+        let stored = to_json_vec(&cursor).unwrap();
+        let mut restored: ShuffleCursor<i32> = from_json(stored).unwrap();
+        assert_eq!(restored, cursor);
+
+        let mut all = first;
+        all.extend(restored.next_page(10));
+        assert_eq!(all, shuffle(RANDOMNESS1, vec![1, 2, 3, 4, 5, 6]));
+    }
+
     #[test]
     fn shuffle_distribution_is_uniform() {
         /// This test takes a vector of characters as data
@@ -143,4 +660,108 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn unshuffle_works() {
+        for len in [0usize, 1, 2, 3, 10, 100, 1000] {
+            let data: Vec<usize> = (0..len).collect();
+            let shuffled = shuffle(RANDOMNESS1, data.clone());
+            assert_eq!(unshuffle(RANDOMNESS1, shuffled), data);
+        }
+
+        let data = vec!["bob", "mary", "su", "marc"];
+        let shuffled = shuffle(RANDOMNESS1, data.clone());
+        assert_ne!(shuffled, data);
+        assert_eq!(unshuffle(RANDOMNESS1, shuffled), data);
+    }
+
+    #[test]
+    fn unshuffle_is_inverse_of_shuffle() {
+        use crate::sub_randomness;
+
+        for randomness in sub_randomness(RANDOMNESS1).take(200) {
+            let len = (randomness[0] % 50) as usize;
+            let data: Vec<usize> = (0..len).collect();
+            // Inverse from both sides
+            assert_eq!(
+                unshuffle(randomness, shuffle(randomness, data.clone())),
+                data
+            );
+            assert_eq!(
+                shuffle(randomness, unshuffle(randomness, data.clone())),
+                data
+            );
+            // The inverse permutation: position of each element after shuffling
+            let shuffled = shuffle(randomness, data.clone());
+            let unshuffled = unshuffle(randomness, data.clone());
+            for (position, element) in shuffled.iter().enumerate() {
+                assert_eq!(unshuffled[*element], position);
+            }
+        }
+    }
+
+    #[test]
+    fn feistel_permutation_is_bijection() {
+        for domain_size in [1u64, 2, 3, 4, 5, 10, 16, 17, 100, 1000, 1025] {
+            let permutation = FeistelPermutation::new(RANDOMNESS1, domain_size, 4);
+            assert_eq!(permutation.domain_size(), domain_size);
+            let mut outputs: Vec<u64> = (0..domain_size).map(|i| permutation.encrypt(i)).collect();
+            for i in 0..domain_size {
+                assert_eq!(permutation.decrypt(outputs[i as usize]), i);
+            }
+            outputs.sort();
+            assert_eq!(outputs, (0..domain_size).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn feistel_permutation_works_for_large_domains() {
+        for domain_size in [1_000_000u64, u32::MAX as u64 + 1, u64::MAX - 1, u64::MAX] {
+            let permutation = FeistelPermutation::new(RANDOMNESS1, domain_size, 6);
+            for i in [0, 1, 42, 999_999, domain_size - 1] {
+                let position = permutation.encrypt(i);
+                assert!(position < domain_size);
+                assert_eq!(permutation.decrypt(position), i);
+            }
+        }
+    }
+
+    #[test]
+    fn feistel_permutation_depends_on_randomness() {
+        let a = FeistelPermutation::new(RANDOMNESS1, 1000, 4);
+        let b = FeistelPermutation::new([0x77; 32], 1000, 4);
+        let order_a: Vec<u64> = (0..1000).map(|i| a.encrypt(i)).collect();
+        let order_b: Vec<u64> = (0..1000).map(|i| b.encrypt(i)).collect();
+        assert_ne!(order_a, order_b);
+        // Not the identity
+        assert_ne!(order_a, (0..1000).collect::<Vec<_>>());
+
+        // Reproducible
+        let again = FeistelPermutation::new(RANDOMNESS1, 1000, 4);
+        assert_eq!(again, a);
+    }
+
+    #[test]
+    #[should_panic = "value 10 is out of the domain [0, 10)"]
+    fn feistel_permutation_encrypt_panics_outside_domain() {
+        FeistelPermutation::new(RANDOMNESS1, 10, 4).encrypt(10);
+    }
+
+    #[test]
+    #[should_panic = "value 10 is out of the domain [0, 10)"]
+    fn feistel_permutation_decrypt_panics_outside_domain() {
+        FeistelPermutation::new(RANDOMNESS1, 10, 4).decrypt(10);
+    }
+
+    #[test]
+    #[should_panic = "domain size must not be 0"]
+    fn feistel_permutation_panics_for_empty_domain() {
+        FeistelPermutation::new(RANDOMNESS1, 0, 4);
+    }
+
+    #[test]
+    #[should_panic = "rounds must be at least 1"]
+    fn feistel_permutation_panics_for_zero_rounds() {
+        FeistelPermutation::new(RANDOMNESS1, 10, 0);
+    }
 }
@@ -17,14 +17,35 @@ use sha2::{Digest, Sha256};
 ///
 /// let env = mock_env();
 /// let result = coinflip(randomness_simulator(&env));
-/// assert_eq!(result, Side::Tails);
+/// assert_eq!(result, Side::Heads);
 ///
 /// ```
 ///
 pub fn randomness_simulator(env: &Env) -> [u8; 32] {
-    let block_height = env.block.height;
+    randomness_simulator_at_height(env.block.height)
+}
+
+/// Creates the predictable randomness seed that [`randomness_simulator`] returns at the given block height
+///
+/// This does not require an `Env`, which makes it possible to compute the seed of a future block,
+/// e.g. for testing time-locked logic.
+/// Warning!! If you need an unpredictalble randomness do not use this function.
+///
+/// ## Examples
+///
+/// ```
+/// use nois::{randomness_simulator, randomness_simulator_at_height};
+/// use cosmwasm_std::testing::mock_env;
+///
+/// let mut env = mock_env();
+/// let future = randomness_simulator_at_height(env.block.height + 10);
+///
+/// env.block.height += 10;
+/// assert_eq!(randomness_simulator(&env), future);
+/// ```
+pub fn randomness_simulator_at_height(height: u64) -> [u8; 32] {
     let mut hasher = Sha256::new();
-    hasher.update(block_height.to_be_bytes());
+    hasher.update(height.to_be_bytes());
     hasher.finalize().into()
 }
 
@@ -36,13 +57,13 @@ mod tests {
         testing::mock_env, Addr, BlockInfo, ContractInfo, Env, HexBinary, Timestamp,
     };
 
-    use crate::randomness_simulator;
+    use crate::{randomness_simulator, randomness_simulator_at_height};
 
     #[test]
     fn simulator_works() {
         let seed = randomness_simulator(&mock_env());
         let result = coinflip(seed);
-        assert_eq!(result, Side::Tails);
+        assert_eq!(result, Side::Heads);
 
         // mock another env with the same block height and make sure the outcome is the same
         let env = Env {
@@ -77,7 +98,23 @@ mod tests {
         assert_ne!(seed, seed3);
         print!("{}", HexBinary::from(seed3));
         let result = coinflip(seed3);
-        assert_eq!(result, Side::Heads);
+        assert_eq!(result, Side::Tails);
+    }
+
+    #[test]
+    fn randomness_simulator_at_height_works() {
+        let mut env = mock_env();
+        for height in [0, 1, 12345, 12347, u64::MAX] {
+            env.block.height = height;
+            assert_eq!(
+                randomness_simulator_at_height(height),
+                randomness_simulator(&env)
+            );
+        }
+        assert_ne!(
+            randomness_simulator_at_height(12345),
+            randomness_simulator_at_height(12346)
+        );
     }
 
     #[test]
@@ -0,0 +1,96 @@
+//! Test vectors for the output stability of randomness transformations.
+//!
+//! Contracts rely on the same randomness leading to the same outcome across versions
+//! of this library. Any change to [`make_prng`], [`int_in_range`], the dice and coinflip
+//! mappings or the shuffle algorithm breaks one of the clearly labeled tests below.
+//! Only update those vectors as part of an intentionally breaking release.
+
+use rand_xoshiro::rand_core::RngCore;
+
+use crate::{
+    coinflip, int_in_range, prng::make_prng, randomness_from_str, roll_dice, shuffle,
+    sub_randomness, Side,
+};
+
+/// The randomness used in most doc examples
+const DOCS_RANDOMNESS: &str = "9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62";
+/// The randomness used in the double dice doc example
+const DOUBLE_DICE_RANDOMNESS: &str =
+    "e2ee31c20afbb20530f6a18d5d96fb20f766fe11799d3611b04bf9edbd2cffcb";
+
+fn docs_randomness() -> [u8; 32] {
+    randomness_from_str(DOCS_RANDOMNESS).unwrap()
+}
+
+#[test]
+fn stability_make_prng() {
+    let mut rng = make_prng(docs_randomness());
+    let values = [rng.next_u64(), rng.next_u64(), rng.next_u64()];
+    assert_eq!(
+        values,
+        [
+            3667324249236650163,
+            2931874555707800370,
+            8318479223802643144
+        ]
+    );
+}
+
+#[test]
+fn stability_sub_randomness() {
+    let mut provider = sub_randomness(docs_randomness());
+    assert_eq!(
+        provider.provide(),
+        [
+            139, 113, 84, 235, 211, 201, 22, 147, 254, 167, 88, 179, 150, 44, 200, 183, 99, 225,
+            191, 64, 154, 3, 17, 198, 251, 49, 145, 76, 246, 94, 91, 38
+        ]
+    );
+}
+
+#[test]
+fn stability_roll_dice() {
+    assert_eq!(roll_dice(docs_randomness()), 2);
+
+    let mut provider = sub_randomness(randomness_from_str(DOUBLE_DICE_RANDOMNESS).unwrap());
+    assert_eq!(roll_dice(provider.provide()), 3);
+    assert_eq!(roll_dice(provider.provide()), 6);
+}
+
+#[test]
+fn stability_coinflip() {
+    assert_eq!(coinflip(docs_randomness()), Side::Tails);
+    assert_eq!(coinflip([0x77; 32]), Side::Heads);
+}
+
+#[test]
+fn stability_int_in_range() {
+    let randomness = [
+        74, 71, 86, 169, 247, 21, 60, 71, 234, 24, 246, 215, 35, 73, 38, 187, 54, 59, 96, 9, 237,
+        27, 215, 103, 14, 230, 28, 48, 51, 114, 203, 219,
+    ];
+    assert_eq!(int_in_range(randomness, 1u16, 33), 12);
+    assert_eq!(int_in_range(randomness, 1u16, 30), 11);
+    assert_eq!(int_in_range(randomness, 1u16, 19), 7);
+
+    let randomness = docs_randomness();
+    assert_eq!(int_in_range(randomness, 1u8, 6), 2);
+    assert_eq!(int_in_range(randomness, 0u32, 1_000_000), 198806);
+    assert_eq!(int_in_range(randomness, -100i64, 100), -10);
+    assert_eq!(
+        int_in_range(randomness, 0u128, u128::MAX),
+        54083539585362691127743783035575548083
+    );
+}
+
+#[test]
+fn stability_shuffle() {
+    assert_eq!(
+        shuffle(docs_randomness(), vec![1, 2, 3, 4]),
+        vec![2, 4, 3, 1]
+    );
+    assert_eq!(
+        shuffle(docs_randomness(), (0..10).collect::<Vec<u32>>()),
+        vec![9, 2, 5, 7, 0, 6, 8, 3, 4, 1]
+    );
+}
@@ -0,0 +1,160 @@
+use rand::Rng;
+
+use crate::{pick_one_owned, prng::make_prng, sub_randomness};
+
+/// Adjectives used by [`random_handle`]
+const ADJECTIVES: [&str; 32] = [
+    "Agile", "Bold", "Brave", "Bright", "Calm", "Clever", "Cosmic", "Curious", "Daring", "Eager",
+    "Fancy", "Fierce", "Gentle", "Golden", "Happy", "Jolly", "Keen", "Lucky", "Mighty", "Nimble",
+    "Noble", "Proud", "Quick", "Quiet", "Rapid", "Shiny", "Silent", "Swift", "Tiny", "Vivid",
+    "Wild", "Witty",
+];
+
+/// Nouns used by [`random_handle`]
+const NOUNS: [&str; 32] = [
+    "Badger", "Bear", "Comet", "Dolphin", "Dragon", "Eagle", "Falcon", "Fox", "Gecko", "Hawk",
+    "Heron", "Koala", "Lion", "Lynx", "Meteor", "Moose", "Nebula", "Otter", "Owl", "Panda",
+    "Panther", "Pulsar", "Quasar", "Raven", "Rocket", "Shark", "Sparrow", "Tiger", "Turtle",
+    "Walrus", "Whale", "Wolf",
+];
+
+/// Generates a readable handle such as "BraveOtter42" from a random adjective, a random
+/// noun and a random number in \[0, 999].
+///
+/// This is handy for demo apps and test fixtures. The output is deterministic for a given
+/// randomness. Handles are not guaranteed to be unique.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_handle};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let handle = random_handle(randomness);
+/// println!("Welcome, {handle}!");
+/// assert_eq!(handle, random_handle(randomness));
+/// ```
+pub fn random_handle(randomness: [u8; 32]) -> String {
+    let mut provider = sub_randomness(randomness);
+    let adjective = pick_one_owned(provider.provide(), ADJECTIVES.to_vec()).unwrap();
+    let noun = pick_one_owned(provider.provide(), NOUNS.to_vec()).unwrap();
+    let number = provider.provide_int_in_range(0u32, 999);
+    format!("{adjective}{noun}{number}")
+}
+
+/// Encodes the randomness as a code of `length` characters from the given alphabet,
+/// e.g. for referral or invite codes.
+///
+/// The characters are drawn uniformly from a PRNG seeded with the randomness, such that
+/// there is no modulo bias for alphabets whose length is not a power of two. The output
+/// is deterministic for a given randomness, alphabet and length. Codes are not guaranteed
+/// to be unique.
+///
+/// Panics if the alphabet contains less than 2 characters.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{encode_base, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Crockford's base 32 avoids the ambiguous characters I, L, O and U
+/// let code = encode_base(randomness, "0123456789ABCDEFGHJKMNPQRSTVWXYZ", 8);
+/// assert_eq!(code.len(), 8);
+/// ```
+pub fn encode_base(randomness: [u8; 32], alphabet: &str, length: usize) -> String {
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    if alphabet.len() < 2 {
+        panic!("alphabet must contain at least 2 characters");
+    }
+    let mut rng = make_prng(randomness);
+    (0..length)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    /// Checks that a handle matches `[A-Z][a-z]+[A-Z][a-z]+\d+`
+    fn is_valid_handle(handle: &str) -> bool {
+        let chars: Vec<char> = handle.chars().collect();
+        let mut pos = 0;
+        for _ in 0..2 {
+            if !chars.get(pos).is_some_and(|c| c.is_ascii_uppercase()) {
+                return false;
+            }
+            pos += 1;
+            let start = pos;
+            while chars.get(pos).is_some_and(|c| c.is_ascii_lowercase()) {
+                pos += 1;
+            }
+            if pos == start {
+                return false;
+            }
+        }
+        pos < chars.len() && chars[pos..].iter().all(|c| c.is_ascii_digit())
+    }
+
+    #[test]
+    fn word_lists_are_valid() {
+        for word in ADJECTIVES.iter().chain(NOUNS.iter()) {
+            assert!(is_valid_handle(&format!("{word}{word}0")), "{word}");
+        }
+    }
+
+    #[test]
+    fn random_handle_works() {
+        // Deterministic
+        let handle = random_handle(RANDOMNESS1);
+        assert_eq!(random_handle(RANDOMNESS1), handle);
+        assert!(is_valid_handle(&handle), "{handle}");
+
+        // Format
+        for randomness in sub_randomness([0x3D; 32]).take(200) {
+            let handle = random_handle(randomness);
+            assert!(is_valid_handle(&handle), "{handle}");
+        }
+
+        // Different randomness leads to different handles
+        assert_ne!(random_handle([0x3D; 32]), random_handle([0x3E; 32]));
+    }
+
+    #[test]
+    fn encode_base_works() {
+        const BASE32: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+        let code = encode_base(RANDOMNESS1, BASE32, 10);
+        assert_eq!(code.chars().count(), 10);
+        assert!(code.chars().all(|c| BASE32.contains(c)), "{code}");
+
+        // Deterministic
+        assert_eq!(encode_base(RANDOMNESS1, BASE32, 10), code);
+        assert_ne!(encode_base([0x77; 32], BASE32, 10), code);
+
+        // Longer codes extend shorter ones
+        assert!(encode_base(RANDOMNESS1, BASE32, 20).starts_with(&code));
+
+        assert_eq!(encode_base(RANDOMNESS1, BASE32, 0), "");
+
+        // Non-ASCII characters
+        let code = encode_base(RANDOMNESS1, "♠♥♦♣", 6);
+        assert_eq!(code.chars().count(), 6);
+        assert!(code.chars().all(|c| "♠♥♦♣".contains(c)));
+
+        // All characters are used
+        let code = encode_base(RANDOMNESS1, BASE32, 1000);
+        assert!(BASE32.chars().all(|c| code.contains(c)));
+    }
+
+    #[test]
+    #[should_panic = "alphabet must contain at least 2 characters"]
+    fn encode_base_panics_for_short_alphabet() {
+        encode_base(RANDOMNESS1, "A", 8);
+    }
+}
@@ -0,0 +1,163 @@
+use rand::Rng;
+
+use crate::{prng::make_prng, shuffle, sub_randomness};
+
+/// Generates a random tree with `node_count` nodes, e.g. for test fixtures.
+///
+/// The tree is returned as a parent-pointer array: the element at index `i` is the parent
+/// of node `i`. Node 0 is the root and has no parent. Every other node's parent is a random
+/// node with a lower index, which guarantees that the result is a tree.
+///
+/// Panics if `node_count` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_tree};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let parents = random_tree(randomness, 5);
+/// assert_eq!(parents.len(), 5);
+/// assert_eq!(parents[0], None);
+/// assert_eq!(parents[1], Some(0));
+/// ```
+pub fn random_tree(randomness: [u8; 32], node_count: usize) -> Vec<Option<usize>> {
+    if node_count == 0 {
+        panic!("node count must be at least 1");
+    }
+    let mut rng = make_prng(randomness);
+    let mut parents = Vec::with_capacity(node_count);
+    parents.push(None);
+    for node in 1..node_count {
+        parents.push(Some(rng.gen_range(0..node)));
+    }
+    parents
+}
+
+/// Generates a random Latin square of size `n`, i.e. an `n`×`n` grid of the symbols
+/// 0 to `n - 1` in which each symbol occurs exactly once per row and column. This is useful
+/// for round-robin scheduling, e.g. to assign rooms to teams for each round.
+///
+/// The square is constructed from the cyclic Latin square `(row + column) % n` by randomly
+/// permuting its rows and columns using [`shuffle`]. Not all Latin squares can be reached
+/// this way for `n` > 3.
+///
+/// Panics if `n` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_latin_square};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Round r, team t plays in room square[r][t]
+/// let square = random_latin_square(randomness, 4);
+/// assert_eq!(square.len(), 4);
+/// for row in &square {
+///     let mut rooms = row.clone();
+///     rooms.sort();
+///     assert_eq!(rooms, vec![0, 1, 2, 3]);
+/// }
+/// ```
+pub fn random_latin_square(randomness: [u8; 32], n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        panic!("size must be at least 1");
+    }
+    let mut provider = sub_randomness(randomness);
+    let rows = shuffle(provider.provide(), (0..n).collect());
+    let columns = shuffle(provider.provide(), (0..n).collect());
+    rows.iter()
+        .map(|row| columns.iter().map(|column| (row + column) % n).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn random_tree_works() {
+        assert_eq!(random_tree(RANDOMNESS1, 1), vec![None]);
+        assert_eq!(random_tree(RANDOMNESS1, 2), vec![None, Some(0)]);
+
+        let parents = random_tree(RANDOMNESS1, 200);
+        assert_eq!(parents.len(), 200);
+
+        // Single root
+        assert_eq!(parents.iter().filter(|p| p.is_none()).count(), 1);
+
+        // Every node reaches the root without cycles
+        for node in 0..parents.len() {
+            let mut current = node;
+            let mut steps = 0;
+            while let Some(parent) = parents[current] {
+                current = parent;
+                steps += 1;
+                assert!(steps < parents.len());
+            }
+            assert_eq!(current, 0);
+        }
+
+        // Reproducible
+        assert_eq!(random_tree(RANDOMNESS1, 200), parents);
+        assert_ne!(random_tree([0xA6; 32], 200), parents);
+    }
+
+    #[test]
+    #[should_panic = "node count must be at least 1"]
+    fn random_tree_panics_for_zero_nodes() {
+        random_tree(RANDOMNESS1, 0);
+    }
+
+    /// Checks that every symbol occurs exactly once per row and column
+    fn is_latin_square(square: &[Vec<usize>]) -> bool {
+        let n = square.len();
+        let expected: Vec<usize> = (0..n).collect();
+        let rows_ok = square.iter().all(|row| {
+            let mut row = row.clone();
+            row.sort();
+            row == expected
+        });
+        let columns_ok = (0..n).all(|column| {
+            let mut column: Vec<usize> = square.iter().map(|row| row[column]).collect();
+            column.sort();
+            column == expected
+        });
+        rows_ok && columns_ok
+    }
+
+    #[test]
+    fn random_latin_square_works() {
+        assert_eq!(random_latin_square(RANDOMNESS1, 1), vec![vec![0]]);
+
+        for n in [2, 3, 5, 8, 13] {
+            for randomness in sub_randomness(RANDOMNESS1).take(20) {
+                let square = random_latin_square(randomness, n);
+                assert_eq!(square.len(), n);
+                assert!(square.iter().all(|row| row.len() == n));
+                assert!(is_latin_square(&square), "{square:?}");
+            }
+        }
+
+        // Reproducible
+        let square = random_latin_square(RANDOMNESS1, 8);
+        assert_eq!(random_latin_square(RANDOMNESS1, 8), square);
+        assert_ne!(random_latin_square([0xA6; 32], 8), square);
+
+        // Not the cyclic square
+        let cyclic: Vec<Vec<usize>> = (0..8)
+            .map(|row| (0..8).map(|column| (row + column) % 8).collect())
+            .collect();
+        assert_ne!(square, cyclic);
+    }
+
+    #[test]
+    #[should_panic = "size must be at least 1"]
+    fn random_latin_square_panics_for_zero_size() {
+        random_latin_square(RANDOMNESS1, 0);
+    }
+}
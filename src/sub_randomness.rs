@@ -1,8 +1,19 @@
-use rand_xoshiro::{rand_core::RngCore, Xoshiro256PlusPlus};
+#[cfg(all(feature = "guard", debug_assertions))]
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use cosmwasm_std::Decimal;
+use rand::distributions::uniform::SampleUniform;
+use rand_xoshiro::{
+    rand_core::{RngCore, SeedableRng},
+    Xoshiro256PlusPlus,
+};
+use sha2::{Digest, Sha256};
 use xxhash_rust::xxh3::xxh3_128;
 
-use crate::prng::make_prng;
+use crate::{int_in_range, prng::make_prng, random_decimal, Int};
 
+#[derive(Clone)]
 pub struct SubRandomnessProvider {
     rng: Xoshiro256PlusPlus,
 }
@@ -13,6 +24,58 @@ impl SubRandomnessProvider {
         self.rng.fill_bytes(&mut out);
         out
     }
+
+    /// Returns the value that [`provide`](Self::provide) would return after skipping
+    /// `round` values, without advancing this provider. I.e. `at_round(0)` is the value
+    /// returned by the next call of `provide`.
+    ///
+    /// The cost of this call is linear in `round`.
+    pub fn at_round(&self, round: u64) -> [u8; 32] {
+        let mut rng = self.rng.clone();
+        let mut out = [0u8; 32];
+        for _ in 0..round {
+            rng.fill_bytes(&mut out);
+        }
+        rng.fill_bytes(&mut out);
+        out
+    }
+
+    /// Returns a commitment to the current position of this provider in its stream.
+    ///
+    /// Two providers created from the same randomness and key that have provided the same
+    /// number of values produce equal checkpoints. This allows storing a checkpoint during a
+    /// long simulation and later asserting that a re-run has not drifted.
+    ///
+    /// The checkpoint is a SHA-256 hash derived from the internal state. It does not reveal
+    /// upcoming values.
+    pub fn checkpoint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"nois/sub_randomness/checkpoint");
+        hasher.update(self.at_round(0));
+        hasher.finalize().into()
+    }
+
+    /// Returns true if and only if [`checkpoint`](Self::checkpoint) equals `expected`.
+    pub fn verify_checkpoint(&self, expected: [u8; 32]) -> bool {
+        self.checkpoint() == expected
+    }
+
+    /// Returns a Decimal d with 0 <= d < 1 derived from the next sub-randomness.
+    ///
+    /// This is equivalent to `random_decimal(provider.provide())`.
+    pub fn provide_decimal(&mut self) -> Decimal {
+        random_decimal(self.provide())
+    }
+
+    /// Returns a random integer in the range \[begin, end] derived from the next sub-randomness.
+    ///
+    /// This is equivalent to `int_in_range(provider.provide(), begin, end)`.
+    pub fn provide_int_in_range<T>(&mut self, begin: T, end: T) -> T
+    where
+        T: SampleUniform + Int,
+    {
+        int_in_range(self.provide(), begin, end)
+    }
 }
 
 impl Iterator for SubRandomnessProvider {
@@ -113,6 +176,317 @@ pub fn sub_randomness(randomness: [u8; 32]) -> Box<SubRandomnessProvider> {
     sub_randomness_with_key(randomness, b"_^default^_")
 }
 
+/// Returns `n` independent seeds derived from the given randomness, e.g. one per
+/// entity in procedural generation.
+///
+/// This is equivalent to `sub_randomness(randomness).take(n).collect()`.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, roll_dice, seeds_for_n};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let monster_strengths: Vec<u8> = seeds_for_n(randomness, 4)
+///     .into_iter()
+///     .map(roll_dice)
+///     .collect();
+/// assert_eq!(monster_strengths.len(), 4);
+/// ```
+pub fn seeds_for_n(randomness: [u8; 32], n: usize) -> Vec<[u8; 32]> {
+    sub_randomness(randomness).take(n).collect()
+}
+
+/// Returns an endless stream of randomness values derived from `base`, e.g. to generate
+/// varied but reproducible inputs when fuzzing code that integrates with nois.
+///
+/// This is the same stream as [`sub_randomness`]. The same base always leads to the
+/// same sequence, such that a failing fuzz case can be replayed from its base alone.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_stream, roll_dice};
+///
+/// for randomness in randomness_stream([0x42; 32]).take(1000) {
+///     let number = roll_dice(randomness);
+///     assert!(number >= 1 && number <= 6);
+/// }
+/// ```
+pub fn randomness_stream(base: [u8; 32]) -> impl Iterator<Item = [u8; 32]> {
+    sub_randomness(base)
+}
+
+/// Returns a stream of randomness values like [`randomness_stream`] for a small integer seed.
+///
+/// The base is the SHA-256 hash of the big endian encoded seed. This is handy for fuzzers
+/// and property tests that produce integer seeds. Do not use this outside of tests since
+/// the output is predictable.
+///
+/// # Example
+///
+/// ```
+/// use nois::{coinflip, randomness_stream_from_u64};
+///
+/// let flips: Vec<_> = randomness_stream_from_u64(7).take(10).map(coinflip).collect();
+/// assert_eq!(flips.len(), 10);
+/// ```
+pub fn randomness_stream_from_u64(seed: u64) -> impl Iterator<Item = [u8; 32]> {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_be_bytes());
+    randomness_stream(hasher.finalize().into())
+}
+
+/// Returns a seed for a node in a tree of seeds, e.g. one per match of a tournament bracket.
+///
+/// The node is identified by its `path` from the root, such as `[round, match]`. The path is
+/// framed unambiguously (length prefix and fixed-width components) and used as the key for
+/// [`sub_randomness_with_key`]. Different paths lead to independent seeds and the same path
+/// always leads to the same seed. The empty path identifies the root.
+///
+/// # Example
+///
+/// ```
+/// use nois::{coinflip, randomness_from_str, seed_for_path};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // Match 3 of round 1
+/// let seed = seed_for_path(randomness, &[1, 3]);
+/// let first_serve = coinflip(seed);
+/// assert_eq!(seed, seed_for_path(randomness, &[1, 3]));
+/// ```
+pub fn seed_for_path(randomness: [u8; 32], path: &[u32]) -> [u8; 32] {
+    let mut key = Vec::with_capacity(b"_^path^_".len() + 8 + 4 * path.len());
+    key.extend_from_slice(b"_^path^_");
+    key.extend_from_slice(&(path.len() as u64).to_be_bytes());
+    for component in path {
+        key.extend_from_slice(&component.to_be_bytes());
+    }
+    sub_randomness_with_key(randomness, key).provide()
+}
+
+/// Returns an independent seed for each trait name, e.g. for the background, body and eyes
+/// of an NFT.
+///
+/// The seed of a trait is derived from the randomness with the trait name as key for
+/// [`sub_randomness_with_key`]. It does not depend on the other trait names or their order,
+/// such that adding a trait later does not change the existing traits of a token.
+///
+/// # Example
+///
+/// ```
+/// use nois::{pick_one_owned, randomness_from_str, trait_seeds};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let seeds = trait_seeds(randomness, &["background", "body", "eyes"]);
+/// let background = pick_one_owned(seeds["background"], vec!["red", "green", "blue"]).unwrap();
+/// let eyes = pick_one_owned(seeds["eyes"], vec!["round", "sleepy", "laser"]).unwrap();
+/// println!("{background} background with {eyes} eyes");
+/// ```
+pub fn trait_seeds(randomness: [u8; 32], trait_names: &[&str]) -> BTreeMap<String, [u8; 32]> {
+    trait_names
+        .iter()
+        .map(|name| {
+            let mut key = Vec::with_capacity(b"_^trait^_".len() + name.len());
+            key.extend_from_slice(b"_^trait^_");
+            key.extend_from_slice(name.as_bytes());
+            let seed = sub_randomness_with_key(randomness, key).provide();
+            (name.to_string(), seed)
+        })
+        .collect()
+}
+
+/// Returns the indices of `nodes` ordered by preference for the given `key` using
+/// highest random weight (rendezvous) hashing.
+///
+/// Every node gets a score derived from the randomness, the key and the node's bytes.
+/// The nodes are then sorted by descending score. Since the score of a node does not depend
+/// on the other nodes, adding a node only inserts it somewhere into the order and removing
+/// a node only removes it. The relative order of all other nodes stays the same.
+/// This is useful for load balancing when nodes join and leave.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, rendezvous_order};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let nodes = ["node-a", "node-b", "node-c"];
+/// let order = rendezvous_order(randomness, b"job 42", &nodes);
+/// let primary = nodes[order[0]];
+/// let fallback = nodes[order[1]];
+/// assert_ne!(primary, fallback);
+/// ```
+pub fn rendezvous_order<T: AsRef<[u8]>>(
+    randomness: [u8; 32],
+    key: &[u8],
+    nodes: &[T],
+) -> Vec<usize> {
+    let mut scored: Vec<([u8; 32], usize)> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let node = node.as_ref();
+            let mut framed = Vec::with_capacity(8 + key.len() + node.len());
+            framed.extend_from_slice(&(key.len() as u64).to_be_bytes());
+            framed.extend_from_slice(key);
+            framed.extend_from_slice(node);
+            (sub_randomness_with_key(randomness, framed).provide(), index)
+        })
+        .collect();
+    // Descending score. Equal scores are practically impossible but ordered by index for determinism.
+    scored.sort_by(|(score_a, index_a), (score_b, index_b)| {
+        score_b.cmp(score_a).then(index_a.cmp(index_b))
+    });
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// Returns a seed and its antithetic counterpart for variance reduction in Monte Carlo simulations.
+///
+/// The seed is derived from the randomness and the counterpart is its bitwise complement.
+/// Interpreting a seed as a big-endian fraction `u` in \[0, 1), the counterpart is
+/// `1 - u - 2^-256`. Averaging an estimator over both values of such a pair reduces the
+/// variance when the estimator is monotone in `u`.
+///
+/// Note that the negative correlation only carries over to transformations that read the
+/// seed bits directly. Transformations that use the seed to initialize a PRNG, such as
+/// [`coinflip`](crate::coinflip), [`int_in_range`] or [`random_decimal`], produce
+/// uncorrelated outputs for the two seeds. Use [`antithetic_decimals`] for an antithetic
+/// pair of uniform decimals.
+///
+/// # Example
+///
+/// ```
+/// use nois::{antithetic_pair, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let (seed, antithetic) = antithetic_pair(randomness);
+/// assert_eq!(antithetic, seed.map(|byte| !byte));
+/// ```
+pub fn antithetic_pair(randomness: [u8; 32]) -> ([u8; 32], [u8; 32]) {
+    let seed = sub_randomness_with_key(randomness, b"_^antithetic^_").provide();
+    (seed, seed.map(|byte| !byte))
+}
+
+/// Returns a uniform decimal `u` in \[0, 1) and its antithetic counterpart `1 - u` for
+/// variance reduction in Monte Carlo simulations.
+///
+/// `u` is the [`random_decimal`] of the first seed of [`antithetic_pair`]. The two values
+/// are perfectly negatively correlated and always sum up to 1. Averaging an estimator over
+/// both values reduces the variance when the estimator is monotone in `u`.
+///
+/// # Example
+///
+/// ```
+/// use cosmwasm_std::Decimal;
+/// use nois::{antithetic_decimals, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let (u, antithetic) = antithetic_decimals(randomness);
+/// assert_eq!(u + antithetic, Decimal::one());
+/// ```
+pub fn antithetic_decimals(randomness: [u8; 32]) -> (Decimal, Decimal) {
+    let (seed, _) = antithetic_pair(randomness);
+    let u = random_decimal(seed);
+    (u, Decimal::one() - u)
+}
+
+/// Returns a deterministic random number generator for the given randomness and key.
+/// Different keys lead to independent streams.
+///
+/// The returned generator implements [`RngCore`](rand::RngCore), so it can be used with
+/// the wider rand ecosystem, e.g. to sample from [`rand_distr`](https://docs.rs/rand_distr)
+/// distributions.
+///
+/// The output is guaranteed to be stable for a given randomness and key within a version of
+/// this library. If outputs need to be reproduced at a later point in time, pin the exact
+/// version of nois. The concrete generator type is an implementation detail.
+///
+/// # Example
+///
+/// ```
+/// use nois::{randomness_from_str, rng_for};
+/// use rand::Rng;
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let mut rng = rng_for(randomness, "damage");
+/// let damage: u32 = rng.gen_range(10..=20);
+/// assert!((10..=20).contains(&damage));
+/// ```
+pub fn rng_for(randomness: [u8; 32], key: impl AsRef<[u8]>) -> impl RngCore + SeedableRng {
+    make_prng(sub_randomness_with_key(randomness, key).provide())
+}
+
+/// Wraps a randomness to catch accidental reuse during development.
+///
+/// A common mistake is to pass the same randomness to two transforms (e.g. two calls of
+/// [`int_in_range`](crate::int_in_range)) and expect independent results. In debug builds
+/// the guard panics when [`RandomnessGuard::apply`] is called a second time. Use
+/// [`RandomnessGuard::provider`] to derive as many independent values as needed instead.
+///
+/// In release builds the check is compiled out and the guard is a zero-cost wrapper.
+/// This type is only available with the `guard` feature.
+///
+/// # Example
+///
+/// ```
+/// use nois::{coinflip, roll_dice, randomness_from_str, RandomnessGuard};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let guard = RandomnessGuard::new(randomness);
+/// let side = guard.apply(coinflip);
+/// // guard.apply(roll_dice); // panics in debug builds
+///
+/// let guard = RandomnessGuard::new(randomness);
+/// let mut provider = guard.provider();
+/// let dice1 = roll_dice(provider.provide());
+/// let dice2 = roll_dice(provider.provide());
+/// ```
+#[cfg(feature = "guard")]
+pub struct RandomnessGuard {
+    randomness: [u8; 32],
+    #[cfg(debug_assertions)]
+    used: Cell<bool>,
+}
+
+#[cfg(feature = "guard")]
+impl RandomnessGuard {
+    /// Wraps the given randomness.
+    pub fn new(randomness: [u8; 32]) -> Self {
+        Self {
+            randomness,
+            #[cfg(debug_assertions)]
+            used: Cell::new(false),
+        }
+    }
+
+    /// Applies a transform to the wrapped randomness.
+    ///
+    /// Panics in debug builds if the randomness was already used by a transform.
+    pub fn apply<T>(&self, transform: impl FnOnce([u8; 32]) -> T) -> T {
+        #[cfg(debug_assertions)]
+        if self.used.replace(true) {
+            panic!("randomness was already used by a transform; use a sub-randomness provider to get independent values");
+        }
+        transform(self.randomness)
+    }
+
+    /// Consumes the guard and returns a provider for independent sub-randomness.
+    ///
+    /// This is equivalent to calling [`sub_randomness`] on the wrapped randomness.
+    pub fn provider(self) -> Box<SubRandomnessProvider> {
+        sub_randomness(self.randomness)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{coinflip, pick, RANDOMNESS1};
@@ -171,6 +545,82 @@ mod tests {
         assert_eq!(provider1.provide(), provider2.provide());
     }
 
+    #[test]
+    fn at_round_works() {
+        let mut provider = sub_randomness(RANDOMNESS1);
+        let mut expected = sub_randomness(RANDOMNESS1);
+        let values: Vec<[u8; 32]> = (0..10).map(|_| expected.provide()).collect();
+
+        for (round, value) in values.iter().enumerate() {
+            assert_eq!(provider.at_round(round as u64), *value);
+        }
+        // The cursor is not moved
+        assert_eq!(provider.provide(), values[0]);
+
+        // Rounds are relative to the current cursor
+        assert_eq!(provider.at_round(0), values[1]);
+        assert_eq!(provider.at_round(3), values[4]);
+        assert_eq!(provider.provide(), values[1]);
+    }
+
+    #[test]
+    fn checkpoint_works() {
+        let mut provider1 = sub_randomness(RANDOMNESS1);
+        let mut provider2 = sub_randomness(RANDOMNESS1);
+        assert_eq!(provider1.checkpoint(), provider2.checkpoint());
+
+        provider1.provide();
+        provider2.provide();
+        let checkpoint = provider1.checkpoint();
+        assert_eq!(provider2.checkpoint(), checkpoint);
+        assert!(provider2.verify_checkpoint(checkpoint));
+
+        // Checkpointing does not advance the provider
+        assert_eq!(provider1.checkpoint(), checkpoint);
+
+        // Diverges after one provider advances
+        provider1.provide();
+        assert_ne!(provider1.checkpoint(), checkpoint);
+        assert!(!provider1.verify_checkpoint(checkpoint));
+        assert!(provider2.verify_checkpoint(checkpoint));
+
+        // Different keys lead to different checkpoints
+        let provider3 = sub_randomness_with_key(RANDOMNESS1, "other");
+        assert_ne!(
+            provider3.checkpoint(),
+            sub_randomness(RANDOMNESS1).checkpoint()
+        );
+    }
+
+    #[test]
+    fn provide_decimal_works() {
+        let mut provider1 = sub_randomness(RANDOMNESS1);
+        let mut provider2 = sub_randomness(RANDOMNESS1);
+        for _ in 0..5 {
+            let value = provider1.provide_decimal();
+            assert!(value < Decimal::one());
+            assert_eq!(value, random_decimal(provider2.provide()));
+        }
+    }
+
+    #[test]
+    fn provide_int_in_range_works() {
+        let mut provider1 = sub_randomness(RANDOMNESS1);
+        let mut provider2 = sub_randomness(RANDOMNESS1);
+        for _ in 0..5 {
+            let value = provider1.provide_int_in_range(-10i32, 10);
+            assert!((-10..=10).contains(&value));
+            assert_eq!(value, int_in_range(provider2.provide(), -10, 10));
+        }
+
+        // Mixed with other calls
+        assert_eq!(provider1.provide(), provider2.provide());
+        assert_eq!(
+            provider1.provide_int_in_range(1u8, 6),
+            int_in_range(provider2.provide(), 1, 6)
+        );
+    }
+
     #[test]
     fn sub_randomness_implements_iterator() {
         let randomness: [u8; 32] = [0x77; 32];
@@ -184,6 +634,316 @@ mod tests {
         println!("v3 = {v3:?}");
         println!("v4 = {v4:?}");
     }
+
+    #[test]
+    fn seeds_for_n_works() {
+        let seeds = seeds_for_n(RANDOMNESS1, 0);
+        assert!(seeds.is_empty());
+
+        let seeds = seeds_for_n(RANDOMNESS1, 5);
+        assert_eq!(seeds.len(), 5);
+        let expected: Vec<[u8; 32]> = sub_randomness(RANDOMNESS1).take(5).collect();
+        assert_eq!(seeds, expected);
+        for (i, seed) in seeds.iter().enumerate() {
+            assert!(!seeds[..i].contains(seed));
+        }
+    }
+
+    #[test]
+    fn seed_for_path_works() {
+        // The same path reproduces
+        assert_eq!(
+            seed_for_path(RANDOMNESS1, &[0, 1]),
+            seed_for_path(RANDOMNESS1, &[0, 1])
+        );
+
+        // Different paths lead to different seeds
+        let paths: [&[u32]; 7] = [&[], &[0], &[1], &[0, 0], &[0, 1], &[1, 0], &[0, 1, 0]];
+        let seeds: Vec<[u8; 32]> = paths
+            .iter()
+            .map(|path| seed_for_path(RANDOMNESS1, path))
+            .collect();
+        for (i, seed) in seeds.iter().enumerate() {
+            assert!(!seeds[..i].contains(seed), "{:?}", paths[i]);
+            assert_ne!(*seed, RANDOMNESS1);
+        }
+
+        // Different randomness leads to different seeds
+        assert_ne!(
+            seed_for_path(RANDOMNESS1, &[0, 1]),
+            seed_for_path([0xA6; 32], &[0, 1])
+        );
+    }
+
+    #[test]
+    fn trait_seeds_works() {
+        let traits = ["background", "body", "eyes", "hat"];
+        let seeds = trait_seeds(RANDOMNESS1, &traits);
+        assert_eq!(seeds.len(), 4);
+
+        // Reproducible and independent of the other traits
+        assert_eq!(trait_seeds(RANDOMNESS1, &traits), seeds);
+        let single = trait_seeds(RANDOMNESS1, &["eyes"]);
+        assert_eq!(single["eyes"], seeds["eyes"]);
+        let reversed = trait_seeds(RANDOMNESS1, &["hat", "eyes", "body", "background"]);
+        assert_eq!(reversed, seeds);
+
+        // Distinct seeds
+        let values: Vec<[u8; 32]> = seeds.values().copied().collect();
+        for (i, value) in values.iter().enumerate() {
+            assert!(!values[..i].contains(value));
+            assert_ne!(*value, RANDOMNESS1);
+        }
+
+        assert!(trait_seeds(RANDOMNESS1, &[]).is_empty());
+    }
+
+    #[test]
+    fn trait_seeds_are_decorrelated() {
+        // Seeds of different traits differ in half of their bits on average
+        let mut total_distance = 0u32;
+        let mut samples = 0u32;
+        for randomness in sub_randomness(RANDOMNESS1).take(1000) {
+            let seeds = trait_seeds(randomness, &["body", "eyes"]);
+            total_distance += seeds["body"]
+                .iter()
+                .zip(seeds["eyes"])
+                .map(|(a, b)| (a ^ b).count_ones())
+                .sum::<u32>();
+            samples += 1;
+        }
+        let mean_distance = total_distance as f64 / samples as f64;
+        assert!(
+            mean_distance > 126.0 && mean_distance < 130.0,
+            "{mean_distance}"
+        );
+    }
+
+    #[test]
+    fn rendezvous_order_works() {
+        let nodes: Vec<String> = (0..10).map(|i| format!("node-{i}")).collect();
+
+        let order = rendezvous_order(RANDOMNESS1, b"key", &nodes);
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+
+        // Deterministic
+        assert_eq!(rendezvous_order(RANDOMNESS1, b"key", &nodes), order);
+
+        // Different keys lead to different orders
+        assert_ne!(rendezvous_order(RANDOMNESS1, b"other key", &nodes), order);
+
+        // Adding a node preserves the relative order of the others
+        let mut extended = nodes.clone();
+        extended.push("node-10".to_string());
+        let extended_order = rendezvous_order(RANDOMNESS1, b"key", &extended);
+        assert_eq!(extended_order.len(), 11);
+        let without_new: Vec<usize> = extended_order.into_iter().filter(|i| *i != 10).collect();
+        assert_eq!(without_new, order);
+
+        // Removing a node preserves the relative order of the others
+        let reduced = &nodes[..9];
+        let reduced_order = rendezvous_order(RANDOMNESS1, b"key", reduced);
+        let without_removed: Vec<usize> = order.iter().copied().filter(|i| *i != 9).collect();
+        assert_eq!(reduced_order, without_removed);
+
+        // Empty
+        let empty: [&str; 0] = [];
+        assert_eq!(
+            rendezvous_order(RANDOMNESS1, b"key", &empty),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn antithetic_pair_works() {
+        let (seed, antithetic) = antithetic_pair(RANDOMNESS1);
+        assert_ne!(seed, antithetic);
+        assert_ne!(seed, RANDOMNESS1);
+        for (a, b) in seed.iter().zip(antithetic.iter()) {
+            assert_eq!(a ^ b, 0xFF);
+        }
+
+        // Reproducible
+        assert_eq!(antithetic_pair(RANDOMNESS1), (seed, antithetic));
+        // Different randomness leads to different pairs
+        assert_ne!(antithetic_pair([0xA6; 32]).0, seed);
+    }
+
+    /// The Pearson correlation coefficient of the given pairs
+    fn correlation(pairs: &[(f64, f64)]) -> f64 {
+        let n = pairs.len() as f64;
+        let mean_a = pairs.iter().map(|(a, _)| a).sum::<f64>() / n;
+        let mean_b = pairs.iter().map(|(_, b)| b).sum::<f64>() / n;
+        let covariance = pairs
+            .iter()
+            .map(|(a, b)| (a - mean_a) * (b - mean_b))
+            .sum::<f64>()
+            / n;
+        let variance_a = pairs.iter().map(|(a, _)| (a - mean_a).powi(2)).sum::<f64>() / n;
+        let variance_b = pairs.iter().map(|(_, b)| (b - mean_b).powi(2)).sum::<f64>() / n;
+        covariance / (variance_a * variance_b).sqrt()
+    }
+
+    #[test]
+    fn antithetic_pair_is_negatively_correlated() {
+        const TEST_SAMPLE_SIZE: usize = 10_000;
+
+        // The leading 64 bits as a fraction in [0, 1)
+        fn fraction(seed: [u8; 32]) -> f64 {
+            u64::from_be_bytes(seed[..8].try_into().unwrap()) as f64 / 2f64.powi(64)
+        }
+
+        let pairs: Vec<(f64, f64)> = sub_randomness(RANDOMNESS1)
+            .take(TEST_SAMPLE_SIZE)
+            .map(|randomness| {
+                let (seed, antithetic) = antithetic_pair(randomness);
+                (fraction(seed), fraction(antithetic))
+            })
+            .collect();
+        let correlation = correlation(&pairs);
+        println!("correlation: {correlation}");
+        assert!(correlation < -0.99);
+    }
+
+    #[test]
+    fn antithetic_decimals_works() {
+        let (u, antithetic) = antithetic_decimals(RANDOMNESS1);
+        assert_eq!(u, random_decimal(antithetic_pair(RANDOMNESS1).0));
+        assert_eq!(u + antithetic, Decimal::one());
+        assert!(u < Decimal::one());
+
+        // Reproducible
+        assert_eq!(antithetic_decimals(RANDOMNESS1), (u, antithetic));
+        assert_ne!(antithetic_decimals([0xA6; 32]).0, u);
+    }
+
+    #[test]
+    fn antithetic_decimals_are_negatively_correlated() {
+        const TEST_SAMPLE_SIZE: usize = 10_000;
+
+        fn to_f64(decimal: Decimal) -> f64 {
+            decimal.atomics().u128() as f64 / 1e18
+        }
+
+        let pairs: Vec<(f64, f64)> = sub_randomness(RANDOMNESS1)
+            .take(TEST_SAMPLE_SIZE)
+            .map(|randomness| {
+                let (u, antithetic) = antithetic_decimals(randomness);
+                (to_f64(u), to_f64(antithetic))
+            })
+            .collect();
+        let correlation = correlation(&pairs);
+        println!("correlation: {correlation}");
+        assert!(correlation < -0.99);
+
+        // u is uniform in [0, 1)
+        let mean = pairs.iter().map(|(u, _)| u).sum::<f64>() / TEST_SAMPLE_SIZE as f64;
+        assert!((mean - 0.5).abs() < 0.02, "{mean}");
+    }
+
+    #[test]
+    fn randomness_stream_works() {
+        // Same as sub_randomness
+        let values: Vec<[u8; 32]> = randomness_stream(RANDOMNESS1).take(100).collect();
+        let expected: Vec<[u8; 32]> = sub_randomness(RANDOMNESS1).take(100).collect();
+        assert_eq!(values, expected);
+
+        // Distinct values
+        for (i, value) in values.iter().enumerate() {
+            assert!(!values[..i].contains(value));
+        }
+
+        // Reproducible
+        let again: Vec<[u8; 32]> = randomness_stream(RANDOMNESS1).take(100).collect();
+        assert_eq!(again, values);
+
+        // Different bases lead to different streams
+        let other: Vec<[u8; 32]> = randomness_stream([0x77; 32]).take(100).collect();
+        assert!(other.iter().all(|value| !values.contains(value)));
+    }
+
+    #[test]
+    fn randomness_stream_from_u64_works() {
+        let values: Vec<[u8; 32]> = randomness_stream_from_u64(42).take(100).collect();
+        for (i, value) in values.iter().enumerate() {
+            assert!(!values[..i].contains(value));
+        }
+        let again: Vec<[u8; 32]> = randomness_stream_from_u64(42).take(100).collect();
+        assert_eq!(again, values);
+
+        assert_ne!(
+            randomness_stream_from_u64(0).next(),
+            randomness_stream_from_u64(1).next()
+        );
+        assert_ne!(
+            randomness_stream_from_u64(u64::MAX).next(),
+            randomness_stream_from_u64(0).next()
+        );
+    }
+
+    #[test]
+    fn rng_for_works() {
+        use rand::distributions::Distribution;
+        use rand_distr::Exp;
+
+        let exp = Exp::new(2.0).unwrap();
+
+        // same randomness and key leads to the same samples
+        let a: Vec<f64> = exp
+            .sample_iter(rng_for(RANDOMNESS1, "wait"))
+            .take(10)
+            .collect();
+        let b: Vec<f64> = exp
+            .sample_iter(rng_for(RANDOMNESS1, "wait"))
+            .take(10)
+            .collect();
+        assert_eq!(a, b);
+        assert!(a.iter().all(|x| *x >= 0.0));
+
+        // different key leads to different samples
+        let c: Vec<f64> = exp
+            .sample_iter(rng_for(RANDOMNESS1, "size"))
+            .take(10)
+            .collect();
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    #[cfg(feature = "guard")]
+    fn randomness_guard_works() {
+        let guard = RandomnessGuard::new(RANDOMNESS1);
+        let side = guard.apply(coinflip);
+        assert_eq!(side, coinflip(RANDOMNESS1));
+
+        let guard = RandomnessGuard::new(RANDOMNESS1);
+        let mut provider = guard.provider();
+        let mut expected = sub_randomness(RANDOMNESS1);
+        assert_eq!(provider.provide(), expected.provide());
+        assert_eq!(provider.provide(), expected.provide());
+    }
+
+    #[test]
+    #[cfg(feature = "guard")]
+    #[cfg(debug_assertions)]
+    #[should_panic = "randomness was already used by a transform"]
+    fn randomness_guard_panics_on_reuse_in_debug() {
+        let guard = RandomnessGuard::new(RANDOMNESS1);
+        let _a = guard.apply(|r| int_in_range(r, 1, 6));
+        let _b = guard.apply(|r| int_in_range(r, 1, 6));
+    }
+
+    #[test]
+    #[cfg(feature = "guard")]
+    #[cfg(not(debug_assertions))]
+    fn randomness_guard_is_noop_in_release() {
+        let guard = RandomnessGuard::new(RANDOMNESS1);
+        let a = guard.apply(|r| int_in_range(r, 1, 6));
+        let b = guard.apply(|r| int_in_range(r, 1, 6));
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn coinflip_distribution_is_uniform() {
         /// This test will generate a huge amount  of subrandomness
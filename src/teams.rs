@@ -0,0 +1,107 @@
+use crate::shuffle;
+
+/// Splits players into two teams of similar total skill.
+///
+/// The players are shuffled and then assigned one by one to the team with the lower
+/// total skill so far (or the smaller team if both totals are equal). The shuffle order
+/// provides the randomness, such that different randomness leads to different but similarly
+/// balanced splits.
+///
+/// This is a heuristic. It guarantees that the difference in total skill does not exceed
+/// the highest single skill value, but it does not necessarily find the most balanced split.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{balance_teams, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let players = vec![
+///     ("alice", 1200),
+///     ("bob", 900),
+///     ("carol", 1500),
+///     ("dave", 1100),
+/// ];
+/// let (team_a, team_b) = balance_teams(randomness, &players);
+/// assert_eq!(team_a.len() + team_b.len(), 4);
+/// ```
+pub fn balance_teams<T: Clone>(randomness: [u8; 32], players: &[(T, u32)]) -> (Vec<T>, Vec<T>) {
+    let shuffled = shuffle(randomness, players.to_vec());
+
+    let mut team_a = Vec::with_capacity(players.len() / 2 + 1);
+    let mut team_b = Vec::with_capacity(players.len() / 2 + 1);
+    let mut skill_a = 0u64;
+    let mut skill_b = 0u64;
+    for (player, skill) in shuffled {
+        let to_a = skill_a < skill_b || (skill_a == skill_b && team_a.len() <= team_b.len());
+        if to_a {
+            team_a.push(player);
+            skill_a += skill as u64;
+        } else {
+            team_b.push(player);
+            skill_b += skill as u64;
+        }
+    }
+    (team_a, team_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    fn total_skill(team: &[&str], players: &[(&str, u32)]) -> u64 {
+        team.iter()
+            .map(|name| players.iter().find(|p| p.0 == *name).unwrap().1 as u64)
+            .sum()
+    }
+
+    #[test]
+    fn balance_teams_works() {
+        let players = vec![
+            ("a", 1200),
+            ("b", 900),
+            ("c", 1500),
+            ("d", 1100),
+            ("e", 700),
+            ("f", 1300),
+            ("g", 1000),
+        ];
+        let max_skill = players.iter().map(|p| p.1).max().unwrap() as u64;
+
+        for subrand in sub_randomness(RANDOMNESS1).take(100) {
+            let (team_a, team_b) = balance_teams(subrand, &players);
+            assert!(!team_a.is_empty());
+            assert!(!team_b.is_empty());
+            assert_eq!(team_a.len() + team_b.len(), players.len());
+
+            let skill_a = total_skill(&team_a, &players);
+            let skill_b = total_skill(&team_b, &players);
+            assert!(skill_a.abs_diff(skill_b) <= max_skill);
+        }
+
+        // Reproducible
+        assert_eq!(
+            balance_teams(RANDOMNESS1, &players),
+            balance_teams(RANDOMNESS1, &players)
+        );
+    }
+
+    #[test]
+    fn balance_teams_works_for_small_inputs() {
+        let (team_a, team_b) = balance_teams::<&str>(RANDOMNESS1, &[]);
+        assert!(team_a.is_empty());
+        assert!(team_b.is_empty());
+
+        let (team_a, team_b) = balance_teams(RANDOMNESS1, &[("a", 5)]);
+        assert_eq!(team_a, ["a"]);
+        assert!(team_b.is_empty());
+
+        // Both teams are non-empty even for zero skills
+        let (team_a, team_b) = balance_teams(RANDOMNESS1, &[("a", 0), ("b", 0)]);
+        assert_eq!(team_a.len(), 1);
+        assert_eq!(team_b.len(), 1);
+    }
+}
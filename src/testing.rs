@@ -0,0 +1,98 @@
+#![cfg(feature = "testing")]
+
+//! This module contains helpers for testing and analysing randomness transformations.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use sha2::{Digest, Sha256};
+
+use crate::sub_randomness;
+
+/// Expands a small seed into a randomness value for tests.
+///
+/// The randomness is the sha256 hash of the big endian encoded seed. This allows test suites
+/// to create many distinct randomness values without hand-crafting `[u8; 32]` arrays.
+/// Do not use this outside of tests since the output is predictable.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{coinflip, test_randomness};
+///
+/// for seed in 0..10 {
+///     let side = coinflip(test_randomness(seed));
+///     println!("{seed}: {side}");
+/// }
+/// ```
+pub fn test_randomness(seed: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// Applies a transform to `rounds` sub-randomness values derived from `base_randomness`
+/// and returns a histogram of the outputs.
+///
+/// This is useful to check the distribution of a randomness transformation,
+/// e.g. for fairness dashboards.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{roll_dice, simulate_distribution};
+///
+/// let histogram = simulate_distribution([0x42; 32], 6_000, roll_dice);
+/// assert_eq!(histogram.len(), 6);
+/// assert_eq!(histogram.values().sum::<usize>(), 6_000);
+/// ```
+pub fn simulate_distribution<T, F>(
+    base_randomness: [u8; 32],
+    rounds: usize,
+    f: F,
+) -> HashMap<T, usize>
+where
+    T: Eq + Hash,
+    F: Fn([u8; 32]) -> T,
+{
+    let mut histogram = HashMap::new();
+    for randomness in sub_randomness(base_randomness).take(rounds) {
+        let count = histogram.entry(f(randomness)).or_insert(0);
+        *count += 1;
+    }
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{coinflip, Side, RANDOMNESS1};
+
+    use super::*;
+
+    #[test]
+    fn test_randomness_works() {
+        // Same seed reproduces
+        assert_eq!(test_randomness(0), test_randomness(0));
+        assert_eq!(test_randomness(42), test_randomness(42));
+
+        // Different seeds produce different arrays
+        let values: Vec<[u8; 32]> = (0..100).map(test_randomness).collect();
+        for (i, value) in values.iter().enumerate() {
+            assert!(!values[..i].contains(value));
+        }
+        assert_ne!(test_randomness(u64::MAX), test_randomness(0));
+    }
+
+    #[test]
+    fn simulate_distribution_works() {
+        let histogram = simulate_distribution(RANDOMNESS1, 100_000, coinflip);
+        assert_eq!(histogram.len(), 2);
+        let heads = histogram[&Side::Heads];
+        let tails = histogram[&Side::Tails];
+        assert_eq!(heads + tails, 100_000);
+        assert!(heads > 49_000 && heads < 51_000);
+        assert!(tails > 49_000 && tails < 51_000);
+
+        let histogram = simulate_distribution(RANDOMNESS1, 0, coinflip);
+        assert!(histogram.is_empty());
+    }
+}
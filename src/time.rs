@@ -0,0 +1,109 @@
+use cosmwasm_std::Timestamp;
+
+use crate::distinct_ints_in_range;
+
+/// Returns `count` distinct timestamps in the window \[start, end), sorted in ascending order.
+///
+/// This is useful for randomized vesting or drip schedules. The timestamps are sampled
+/// uniformly with nanosecond precision using [`distinct_ints_in_range`].
+///
+/// Returns an error if `end` is before `start` or if the window contains fewer than `count`
+/// distinct nanosecond values.
+///
+/// ## Example
+///
+/// ```
+/// use cosmwasm_std::Timestamp;
+/// use nois::{randomness_from_str, random_schedule};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// // 4 drips within one day
+/// let start = Timestamp::from_seconds(1_700_000_000);
+/// let end = start.plus_days(1);
+/// let schedule = random_schedule(randomness, 4, start, end).unwrap();
+/// assert_eq!(schedule.len(), 4);
+/// assert!(schedule[0] >= start && schedule[3] < end);
+/// ```
+pub fn random_schedule(
+    randomness: [u8; 32],
+    count: usize,
+    start: Timestamp,
+    end: Timestamp,
+) -> Result<Vec<Timestamp>, String> {
+    if end < start {
+        return Err(String::from("End must not be before start"));
+    }
+    let window = end.nanos() - start.nanos();
+    if count as u128 > window as u128 {
+        return Err(format!(
+            "Time window is too small for {count} distinct timestamps"
+        ));
+    }
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut offsets = distinct_ints_in_range(randomness, count, 0, window - 1);
+    offsets.sort_unstable();
+    Ok(offsets
+        .into_iter()
+        .map(|offset| start.plus_nanos(offset))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    #[test]
+    fn random_schedule_works() {
+        let start = Timestamp::from_seconds(1_700_000_000);
+        let end = start.plus_days(30);
+
+        for randomness in sub_randomness(RANDOMNESS1).take(20) {
+            let schedule = random_schedule(randomness, 12, start, end).unwrap();
+            assert_eq!(schedule.len(), 12);
+            for (i, timestamp) in schedule.iter().enumerate() {
+                assert!(*timestamp >= start && *timestamp < end);
+                // Sorted and distinct
+                if i > 0 {
+                    assert!(schedule[i - 1] < *timestamp);
+                }
+            }
+        }
+
+        // Deterministic
+        assert_eq!(
+            random_schedule(RANDOMNESS1, 12, start, end).unwrap(),
+            random_schedule(RANDOMNESS1, 12, start, end).unwrap()
+        );
+
+        // Window exactly as large as count
+        let schedule = random_schedule(RANDOMNESS1, 3, start, start.plus_nanos(3)).unwrap();
+        assert_eq!(
+            schedule,
+            vec![start, start.plus_nanos(1), start.plus_nanos(2)]
+        );
+
+        // Nothing to schedule
+        let schedule = random_schedule(RANDOMNESS1, 0, start, start).unwrap();
+        assert_eq!(schedule, Vec::<Timestamp>::new());
+    }
+
+    #[test]
+    fn random_schedule_fails_for_invalid_window() {
+        let start = Timestamp::from_seconds(1_700_000_000);
+
+        let err = random_schedule(RANDOMNESS1, 1, start, start.minus_nanos(1)).unwrap_err();
+        assert_eq!(err, "End must not be before start");
+
+        let err = random_schedule(RANDOMNESS1, 4, start, start.plus_nanos(3)).unwrap_err();
+        assert_eq!(err, "Time window is too small for 4 distinct timestamps");
+
+        let err = random_schedule(RANDOMNESS1, 1, start, start).unwrap_err();
+        assert_eq!(err, "Time window is too small for 1 distinct timestamps");
+    }
+}
@@ -0,0 +1,63 @@
+use crate::int_in_range;
+
+/// Returns a backoff delay in milliseconds in the range \[base_ms, base_ms + max_jitter_ms].
+///
+/// This is `base_ms` plus a random jitter derived from the randomness, e.g. for retries
+/// of off-chain relayers. The result is deterministic for a given randomness.
+///
+/// Panics if `base_ms + max_jitter_ms` exceeds the u64 range.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{random_jitter, randomness_from_str};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let delay = random_jitter(randomness, 1_000, 250);
+/// assert!(delay >= 1_000 && delay <= 1_250);
+/// ```
+pub fn random_jitter(randomness: [u8; 32], base_ms: u64, max_jitter_ms: u64) -> u64 {
+    if base_ms.checked_add(max_jitter_ms).is_none() {
+        panic!("base_ms + max_jitter_ms exceeds the u64 range");
+    }
+    base_ms + int_in_range(randomness, 0, max_jitter_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    #[test]
+    fn random_jitter_works() {
+        // Deterministic
+        assert_eq!(
+            random_jitter(RANDOMNESS1, 1_000, 250),
+            random_jitter(RANDOMNESS1, 1_000, 250)
+        );
+
+        // Bounds
+        for randomness in sub_randomness(RANDOMNESS1).take(1_000) {
+            let delay = random_jitter(randomness, 1_000, 250);
+            assert!((1_000..=1_250).contains(&delay));
+        }
+
+        // No jitter
+        assert_eq!(random_jitter(RANDOMNESS1, 500, 0), 500);
+        assert_eq!(random_jitter(RANDOMNESS1, 0, 0), 0);
+
+        // Largest values that do not overflow
+        let delay = random_jitter(RANDOMNESS1, u64::MAX - 10, 10);
+        assert!(delay >= u64::MAX - 10);
+        random_jitter(RANDOMNESS1, 0, u64::MAX);
+        assert_eq!(random_jitter(RANDOMNESS1, u64::MAX, 0), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic = "base_ms + max_jitter_ms exceeds the u64 range"]
+    fn random_jitter_panics_on_overflow() {
+        random_jitter(RANDOMNESS1, u64::MAX - 10, 11);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{shuffle, sub_randomness};
+
+/// Places ranked players into a single elimination bracket such that top seeds meet as
+/// late as possible.
+///
+/// `ranked_players` is ordered from the strongest (seed 1) to the weakest player. The result
+/// is the bracket in slot order, i.e. the players at index 0 and 1 meet in the first round,
+/// then 2 and 3 and so on. The standard placement is used, e.g. 1 vs 8, 4 vs 5, 2 vs 7 and
+/// 3 vs 6 for 8 players, which puts seeds 1 and 2 in opposite halves and seeds 1-4 in different
+/// quarters.
+///
+/// Seeds of the same tier (3-4, 5-8, 9-16, ...) are considered equal. The randomness decides
+/// which of the tier's slots each of them gets. Seeds 1 and 2 are always placed at the top
+/// of the upper and the lower half of the bracket.
+///
+/// Panics if the number of players is not a power of two. Use [`seed_bracket_with_byes`]
+/// for other numbers of players.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, seed_bracket};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let ranked = vec![1, 2, 3, 4, 5, 6, 7, 8];
+/// let bracket = seed_bracket(randomness, ranked);
+/// // Seed 1 plays one of the seeds 5-8 in the first round
+/// assert_eq!(bracket[0], 1);
+/// assert!((5..=8).contains(&bracket[1]));
+/// // Seed 2 is in the other half
+/// assert!(bracket[4..].contains(&2));
+/// ```
+pub fn seed_bracket<T>(randomness: [u8; 32], ranked_players: Vec<T>) -> Vec<T> {
+    if !ranked_players.len().is_power_of_two() {
+        panic!("number of players must be a power of two");
+    }
+    place_players(randomness, ranked_players)
+        .into_iter()
+        .map(|slot| slot.expect("a full bracket has no byes"))
+        .collect()
+}
+
+/// Places ranked players into a single elimination bracket like [`seed_bracket`] and fills
+/// it up to the next power of two with byes (`None`).
+///
+/// The byes are given to the highest seeds, i.e. with 6 players seeds 1 and 2 advance to the
+/// second round without playing. Within a tier, the randomness only decides among the players
+/// that are treated the same, such that a lower seed never gets a bye while a higher seed has
+/// to play.
+///
+/// For a power of two number of players the result is the result of [`seed_bracket`]
+/// wrapped in `Some`.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, seed_bracket_with_byes};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let ranked = vec![1, 2, 3, 4, 5, 6];
+/// let bracket = seed_bracket_with_byes(randomness, ranked);
+/// assert_eq!(bracket.len(), 8);
+/// // Seeds 1 and 2 get a bye
+/// assert_eq!(bracket[..2], [Some(1), None]);
+/// assert_eq!(bracket[4..6], [Some(2), None]);
+/// ```
+pub fn seed_bracket_with_byes<T>(randomness: [u8; 32], ranked_players: Vec<T>) -> Vec<Option<T>> {
+    place_players(randomness, ranked_players)
+}
+
+/// Places the players into a bracket of the next power of two size. Seed positions without
+/// a player are byes.
+fn place_players<T>(randomness: [u8; 32], ranked_players: Vec<T>) -> Vec<Option<T>> {
+    let n = ranked_players.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let size = n.next_power_of_two();
+
+    // The slot of each seed (0-based) in standard placement
+    let mut slot_of_seed = vec![0usize; size];
+    for (slot, seed) in standard_order(size).into_iter().enumerate() {
+        slot_of_seed[seed] = slot;
+    }
+
+    let mut provider = sub_randomness(randomness);
+    let mut slots: Vec<Option<T>> = (0..size).map(|_| None).collect();
+    let mut players = ranked_players.into_iter();
+    let mut tier_start = 0;
+    while tier_start < n {
+        let tier_end = if tier_start == 0 { 1 } else { tier_start * 2 };
+        // The seeds in [tier_start, bye_end) play against a bye, since the opponent
+        // of seed `s` is `size - 1 - s`
+        let bye_end = (size - n).clamp(tier_start, tier_end);
+        let player_end = n.min(tier_end);
+        for (group_start, group_end) in [(tier_start, bye_end), (bye_end, player_end)] {
+            if group_start >= group_end {
+                continue;
+            }
+            let group: Vec<T> = players.by_ref().take(group_end - group_start).collect();
+            let group = shuffle(provider.provide(), group);
+            for (offset, player) in group.into_iter().enumerate() {
+                slots[slot_of_seed[group_start + offset]] = Some(player);
+            }
+        }
+        tier_start = tier_end;
+    }
+    slots
+}
+
+/// Returns the 0-based seeds in bracket slot order for `n` players, e.g.
+/// `[0, 7, 3, 4, 1, 6, 2, 5]` for 8 players. `n` must be a power of two.
+fn standard_order(n: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < n {
+        let size = order.len() * 2;
+        order = order
+            .into_iter()
+            .flat_map(|seed| [seed, size - 1 - seed])
+            .collect();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn standard_order_works() {
+        assert_eq!(standard_order(1), vec![0]);
+        assert_eq!(standard_order(2), vec![0, 1]);
+        assert_eq!(standard_order(4), vec![0, 3, 1, 2]);
+        assert_eq!(standard_order(8), vec![0, 7, 3, 4, 1, 6, 2, 5]);
+    }
+
+    #[test]
+    fn seed_bracket_works() {
+        assert_eq!(seed_bracket(RANDOMNESS1, vec!["a"]), vec!["a"]);
+        assert_eq!(seed_bracket(RANDOMNESS1, vec!["a", "b"]), vec!["a", "b"]);
+
+        for randomness in sub_randomness(RANDOMNESS1).take(50) {
+            let bracket = seed_bracket(randomness, (1..=16).collect());
+
+            // All players are placed once
+            let mut sorted = bracket.clone();
+            sorted.sort();
+            assert_eq!(sorted, (1..=16).collect::<Vec<_>>());
+
+            // Seeds 1 and 2 are in opposite halves
+            assert_eq!(bracket[0], 1);
+            assert_eq!(bracket[8], 2);
+            assert!(bracket[..8].contains(&1) && bracket[8..].contains(&2));
+
+            // Seeds 1-4 are in different quarters
+            for quarter in bracket.chunks(4) {
+                assert_eq!(quarter.iter().filter(|seed| **seed <= 4).count(), 1);
+            }
+
+            // Seeds 1-8 do not meet in the first round
+            for pair in bracket.chunks(2) {
+                assert!(pair[0] <= 8 && pair[1] > 8);
+            }
+        }
+    }
+
+    #[test]
+    fn seed_bracket_randomizes_within_tiers() {
+        let brackets: Vec<Vec<u32>> = sub_randomness(RANDOMNESS1)
+            .take(50)
+            .map(|randomness| seed_bracket(randomness, (1..=8).collect()))
+            .collect();
+        // Seeds 3 and 4 swap their places
+        assert!(brackets.iter().any(|bracket| bracket[2] == 3));
+        assert!(brackets.iter().any(|bracket| bracket[2] == 4));
+        // Reproducible
+        let randomness = sub_randomness(RANDOMNESS1).next().unwrap();
+        assert_eq!(seed_bracket(randomness, (1..=8).collect()), brackets[0]);
+    }
+
+    #[test]
+    fn seed_bracket_with_byes_works() {
+        assert_eq!(
+            seed_bracket_with_byes::<u32>(RANDOMNESS1, vec![]),
+            Vec::<Option<u32>>::new()
+        );
+        assert_eq!(
+            seed_bracket_with_byes(RANDOMNESS1, vec!["a"]),
+            vec![Some("a")]
+        );
+        assert_eq!(
+            seed_bracket_with_byes(RANDOMNESS1, vec!["a", "b", "c"]),
+            vec![Some("a"), None, Some("b"), Some("c")]
+        );
+
+        for randomness in sub_randomness(RANDOMNESS1).take(50) {
+            // Same as seed_bracket for a full bracket
+            let full: Vec<Option<u32>> = seed_bracket(randomness, (1..=8).collect())
+                .into_iter()
+                .map(Some)
+                .collect();
+            assert_eq!(seed_bracket_with_byes(randomness, (1..=8).collect()), full);
+
+            for n in [5u32, 6, 7, 9, 12, 13] {
+                let bracket = seed_bracket_with_byes(randomness, (1..=n).collect());
+                let size = (n as usize).next_power_of_two();
+                assert_eq!(bracket.len(), size);
+                let byes = size - n as usize;
+
+                // All players are placed once
+                let mut players: Vec<u32> = bracket.iter().flatten().copied().collect();
+                players.sort();
+                assert_eq!(players, (1..=n).collect::<Vec<_>>());
+
+                // Seed 1 always gets a bye
+                assert_eq!(bracket[0], Some(1));
+                assert_eq!(bracket[1], None);
+
+                // Exactly the top seeds get the byes and no two byes meet
+                for pair in bracket.chunks(2) {
+                    match pair {
+                        [Some(player), None] | [None, Some(player)] => {
+                            assert!(*player as usize <= byes, "{bracket:?}")
+                        }
+                        [Some(a), Some(b)] => assert!(*a as usize > byes && *b as usize > byes),
+                        _ => panic!("two byes meet: {bracket:?}"),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "number of players must be a power of two"]
+    fn seed_bracket_panics_for_non_power_of_two() {
+        seed_bracket(RANDOMNESS1, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic = "number of players must be a power of two"]
+    fn seed_bracket_panics_for_empty() {
+        seed_bracket::<u32>(RANDOMNESS1, vec![]);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::int_in_range;
+
+/// Returns the index of the player who starts the given round.
+///
+/// A random base offset in \[0, player_count) is derived from the randomness once per game.
+/// The starting player then rotates by one per round, such that within every `player_count`
+/// consecutive rounds each player starts exactly once.
+///
+/// Panics if `player_count` is 0.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, starting_player};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let first = starting_player(randomness, 4, 0);
+/// let second = starting_player(randomness, 4, 1);
+/// assert_eq!(second, (first + 1) % 4);
+/// ```
+pub fn starting_player(randomness: [u8; 32], player_count: usize, round: u64) -> usize {
+    if player_count == 0 {
+        panic!("player count must be at least 1");
+    }
+    let player_count = player_count as u64;
+    let offset = int_in_range(randomness, 0, player_count - 1);
+    ((offset + round % player_count) % player_count) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{sub_randomness, RANDOMNESS1};
+
+    use super::*;
+
+    #[test]
+    fn starting_player_works() {
+        assert_eq!(starting_player(RANDOMNESS1, 1, 0), 0);
+        assert_eq!(starting_player(RANDOMNESS1, 1, 17), 0);
+
+        for randomness in sub_randomness(RANDOMNESS1).take(20) {
+            let first = starting_player(randomness, 5, 0);
+            assert!(first < 5);
+
+            // Each player starts once in a full rotation, starting at any round
+            for start in [0u64, 3, 1_000_000] {
+                let mut starters: Vec<usize> = (start..start + 5)
+                    .map(|round| starting_player(randomness, 5, round))
+                    .collect();
+                starters.sort();
+                assert_eq!(starters, vec![0, 1, 2, 3, 4]);
+            }
+
+            // Rotation repeats
+            assert_eq!(starting_player(randomness, 5, 5), first);
+            assert_eq!(
+                starting_player(randomness, 5, u64::MAX),
+                starting_player(randomness, 5, u64::MAX % 5)
+            );
+        }
+
+        // The base offset is random
+        let offsets: Vec<usize> = sub_randomness(RANDOMNESS1)
+            .take(50)
+            .map(|randomness| starting_player(randomness, 5, 0))
+            .collect();
+        for player in 0..5 {
+            assert!(offsets.contains(&player));
+        }
+    }
+
+    #[test]
+    #[should_panic = "player count must be at least 1"]
+    fn starting_player_panics_for_no_players() {
+        starting_player(RANDOMNESS1, 0, 0);
+    }
+}
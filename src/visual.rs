@@ -0,0 +1,99 @@
+use crate::sub_randomness;
+
+/// The number of distinct hue steps used by [`random_palette`]
+const HUE_STEPS: u32 = 6 * 256;
+
+/// Generates `count` visually distinct colors as RGB tuples, e.g. for NFT generators.
+///
+/// The hues are spaced evenly around the color wheel starting at a random offset.
+/// Saturation and value are randomized slightly per color. The output is deterministic
+/// for a given randomness and count.
+///
+/// ## Example
+///
+/// ```
+/// use nois::{randomness_from_str, random_palette};
+///
+/// let randomness = randomness_from_str("9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62").unwrap();
+///
+/// let palette = random_palette(randomness, 5);
+/// assert_eq!(palette.len(), 5);
+/// for (r, g, b) in palette {
+///     println!("#{r:02x}{g:02x}{b:02x}");
+/// }
+/// ```
+pub fn random_palette(randomness: [u8; 32], count: usize) -> Vec<(u8, u8, u8)> {
+    let mut provider = sub_randomness(randomness);
+    let offset = provider.provide_int_in_range(0, HUE_STEPS - 1);
+    (0..count)
+        .map(|i| {
+            let step = (i as u64 * HUE_STEPS as u64 / count as u64) as u32;
+            let hue = (offset + step) % HUE_STEPS;
+            // Saturation in [70%, 100%] and value in [80%, 100%]
+            let saturation = provider.provide_int_in_range(179u8, 255);
+            let value = provider.provide_int_in_range(204u8, 255);
+            hsv_to_rgb(hue, saturation, value)
+        })
+        .collect()
+}
+
+/// Converts a color from HSV to RGB using integer arithmetic.
+/// `hue` is in [0, HUE_STEPS), i.e. 256 steps per sector of the color wheel.
+fn hsv_to_rgb(hue: u32, saturation: u8, value: u8) -> (u8, u8, u8) {
+    let s = saturation as u32;
+    let v = value as u32;
+    let region = hue / 256;
+    let remainder = hue % 256;
+
+    let p = (v * (255 - s) / 255) as u8;
+    let q = (v * (255 - s * remainder / 255) / 255) as u8;
+    let t = (v * (255 - s * (255 - remainder) / 255) / 255) as u8;
+
+    match region {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RANDOMNESS1;
+
+    use super::*;
+
+    #[test]
+    fn hsv_to_rgb_works() {
+        // Gray scale
+        assert_eq!(hsv_to_rgb(0, 0, 0), (0, 0, 0));
+        assert_eq!(hsv_to_rgb(700, 0, 255), (255, 255, 255));
+        // Primary and secondary colors
+        assert_eq!(hsv_to_rgb(0, 255, 255), (255, 0, 0));
+        assert_eq!(hsv_to_rgb(256, 255, 255), (255, 255, 0));
+        assert_eq!(hsv_to_rgb(512, 255, 255), (0, 255, 0));
+        assert_eq!(hsv_to_rgb(768, 255, 255), (0, 255, 255));
+        assert_eq!(hsv_to_rgb(1024, 255, 255), (0, 0, 255));
+        assert_eq!(hsv_to_rgb(1280, 255, 255), (255, 0, 255));
+    }
+
+    #[test]
+    fn random_palette_works() {
+        assert!(random_palette(RANDOMNESS1, 0).is_empty());
+        assert_eq!(random_palette(RANDOMNESS1, 1).len(), 1);
+
+        let palette = random_palette(RANDOMNESS1, 12);
+        assert_eq!(palette.len(), 12);
+
+        // Colors are not all identical
+        for (i, color) in palette.iter().enumerate() {
+            assert!(!palette[..i].contains(color));
+        }
+
+        // Deterministic
+        assert_eq!(random_palette(RANDOMNESS1, 12), palette);
+        assert_ne!(random_palette([0xA6; 32], 12), palette);
+    }
+}